@@ -0,0 +1,119 @@
+// Client-side search over the build-time index in /search-index.json.
+(function () {
+    var INDEX_VERSION = 1;
+    var WEIGHTS = { title: 10, tags: 6, headings: 4, body: 1 };
+    var pages = null;
+    var loading = null;
+
+    function loadIndex() {
+        if (!loading) {
+            loading = fetch("/search-index.json")
+                .then(function (response) {
+                    return response.json();
+                })
+                .then(function (index) {
+                    if (index.version !== INDEX_VERSION) {
+                        throw new Error("Unsupported search index version " + index.version);
+                    }
+                    pages = index.pages.map(function (row) {
+                        var page = {};
+                        index.fields.forEach(function (field, i) {
+                            page[field] = row[i];
+                        });
+                        page.lower = {
+                            title: page.title.toLowerCase(),
+                            tags: page.tags.join(" ").toLowerCase(),
+                            headings: page.headings.join(" ").toLowerCase(),
+                            body: page.body.toLowerCase(),
+                        };
+                        return page;
+                    });
+                });
+        }
+        return loading;
+    }
+
+    function search(query) {
+        var terms = query.toLowerCase().split(/\s+/).filter(Boolean);
+        if (!terms.length) return [];
+        return pages
+            .map(function (page) {
+                var score = 0;
+                for (var i = 0; i < terms.length; i++) {
+                    var termScore = 0;
+                    for (var field in WEIGHTS) {
+                        if (page.lower[field].indexOf(terms[i]) !== -1) {
+                            termScore += WEIGHTS[field];
+                        }
+                    }
+                    // Every term must match somewhere
+                    if (!termScore) return null;
+                    score += termScore;
+                }
+                return { page: page, score: score };
+            })
+            .filter(Boolean)
+            .sort(function (a, b) {
+                return b.score - a.score;
+            })
+            .slice(0, 10);
+    }
+
+    function snippet(page, query) {
+        var term = query.toLowerCase().split(/\s+/).filter(Boolean)[0] || "";
+        var at = page.lower.body.indexOf(term);
+        if (at === -1) return page.body.slice(0, 120);
+        var start = Math.max(0, at - 40);
+        return (start ? "…" : "") + page.body.slice(start, start + 120) + "…";
+    }
+
+    function render(list, results, query) {
+        list.innerHTML = "";
+        if (!results.length) {
+            var empty = document.createElement("li");
+            empty.textContent = "No results";
+            list.appendChild(empty);
+        }
+        results.forEach(function (result) {
+            var item = document.createElement("li");
+            var link = document.createElement("a");
+            link.href = result.page.url;
+            link.textContent = result.page.title;
+            var text = document.createElement("p");
+            text.textContent = snippet(result.page, query);
+            item.appendChild(link);
+            item.appendChild(text);
+            list.appendChild(item);
+        });
+        list.hidden = false;
+    }
+
+    function bind(form) {
+        var input = form.querySelector("input[type=search]");
+        var list = form.querySelector(".search-results");
+        if (!input || !list) return;
+
+        input.addEventListener("focus", loadIndex);
+        input.addEventListener("input", function () {
+            var query = input.value.trim();
+            if (!query) {
+                list.hidden = true;
+                return;
+            }
+            loadIndex().then(function () {
+                render(list, search(query), query);
+            });
+        });
+        input.addEventListener("keydown", function (e) {
+            if (e.key === "Escape") {
+                input.value = "";
+                list.hidden = true;
+            }
+        });
+    }
+
+    // Every search form on the page, e.g. the nav box and the one on the 404 page
+    document.addEventListener("DOMContentLoaded", function () {
+        document.querySelectorAll("form.search").forEach(bind);
+    });
+})();
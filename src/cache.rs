@@ -0,0 +1,451 @@
+//! Incremental build cache.
+//!
+//! Each project gets a manifest in `.site-cache/` recording a key for its
+//! configuration, the content hash of every source file it read, and the
+//! metadata and output files produced while processing it. A project whose key
+//! and inputs are unchanged, and whose output files all still exist, is
+//! restored from the manifest instead of being rebuilt.
+
+use crate::forge::ForgeLink;
+use crate::git::Contributor;
+use crate::project::Project;
+use crate::site::Site;
+use crate::validate::ValidationConfig;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Directory the build cache is stored in.
+pub const CACHE_DIR: &str = ".site-cache";
+
+/// Hash recorded for inputs that don't exist.
+const MISSING: &str = "-";
+
+/// Build cache for project pages.
+#[derive(Debug)]
+pub struct BuildCache {
+    /// Directory holding the manifests
+    dir: PathBuf,
+    /// Whether every project is rebuilt regardless of the cache
+    force: bool,
+    /// Hash of the generator executable, site settings, layout and shared assets
+    generator: u64,
+    /// Names of projects rebuilt during this build
+    pub rebuilt: Vec<String>,
+    /// Names of projects restored from the cache during this build
+    pub reused: Vec<String>,
+}
+
+impl BuildCache {
+    /// Creates a build cache.
+    ///
+    /// The running executable is part of the key, so any change to the generator's
+    /// code invalidates every entry.
+    ///
+    /// # Arguments
+    /// * `dir` - The cache directory, usually `CACHE_DIR`
+    /// * `force` - Whether to ignore cached results and rebuild everything
+    /// * `site` - The site settings every page is rendered with
+    /// * `layout` - The page layout every project is rendered with
+    /// * `assets` - Shared files that affect project output, such as the stylesheet
+    ///
+    /// # Returns
+    /// * `BuildCache` - The cache; nothing is read until `restore` is called
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        force: bool,
+        site: &Site,
+        layout: &str,
+        assets: &[&str],
+    ) -> Self {
+        let executable = env::current_exe()
+            .map(|path| file_hash(&path))
+            .unwrap_or_else(|_| MISSING.to_string());
+        // The build date only matters for scheduled pages, which are never cached, and
+        // validation runs on the output rather than shaping it
+        let site = Site {
+            build_date: String::new(),
+            validation: ValidationConfig::default(),
+            ..site.clone()
+        };
+        let mut generator = hash(FNV_OFFSET, env!("CARGO_PKG_VERSION").as_bytes());
+        generator = hash(generator, executable.as_bytes());
+        generator = hash(generator, format!("{:?}", site).as_bytes());
+        generator = hash(generator, layout.as_bytes());
+        for asset in assets {
+            generator = hash(generator, file_hash(Path::new(asset)).as_bytes());
+        }
+        Self {
+            dir: dir.into(),
+            force,
+            generator,
+            rebuilt: Vec::new(),
+            reused: Vec::new(),
+        }
+    }
+
+    /// Computes the cache key of a project from its configuration.
+    ///
+    /// Call this after `project::prepare_projects`, so the key covers the Git,
+    /// front matter and package metadata it fills in, and before processing.
+    pub fn key(&self, project: &Project) -> String {
+        format!(
+            "{:016x}",
+            hash(self.generator, format!("{:?}", project).as_bytes())
+        )
+    }
+
+    /// Restores a project from its manifest if nothing it depends on has changed.
+    ///
+    /// # Arguments
+    /// * `project` - The project as configured in code
+    /// * `key` - The key returned by `key` for the same project
+    ///
+    /// # Returns
+    /// * `Option<Project>` - The processed project, or None if it must be rebuilt
+    pub fn restore(&self, project: &Project, key: &str) -> Option<Project> {
+        if self.force || !Path::new(&project.output_dir).join("index.html").exists() {
+            return None;
+        }
+        let manifest = fs::read_to_string(self.manifest_path(project, "manifest")).ok()?;
+        let mut restored = project.clone();
+        restored.tags.clear();
+        restored.aliases.clear();
+        restored.subpages.clear();
+        restored.contributors.clear();
+        restored.content = Some(fs::read_to_string(self.manifest_path(project, "html")).ok()?);
+
+        let mut key_matches = false;
+        for line in manifest.lines() {
+            let (field, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = unescape(value);
+            match field {
+                "key" => key_matches = value == key,
+                "input" => {
+                    let (hash, path) = value.split_once(' ')?;
+                    if file_hash(&Path::new(&project.source_dir).join(path)) != hash {
+                        return None;
+                    }
+                }
+                "output" if !Path::new(&project.output_dir).join(&value).is_file() => {
+                    return None;
+                }
+                "github_url" => restored.github_url = Some(value),
+                "title" => restored.title = Some(value),
+                "description" => restored.description = Some(value),
+                "logo" => restored.logo = Some(value),
+                "social_image" => restored.social_image = Some(value),
+                "status" => restored.status = Some(value),
+                "featured" => restored.featured = value.parse().ok(),
+                "order" => restored.order = value.parse().ok(),
+                "tag" => restored.tags.push(value),
+                "alias" => restored.aliases.push(value),
+                "subpage" => restored.subpages.push(value),
+                "contributor" => {
+                    let mut fields = value.splitn(3, ' ');
+                    let commits = fields.next()?.parse().ok()?;
+                    let email = fields.next()?.to_string();
+                    let name = fields.next()?.to_string();
+                    restored.contributors.push(Contributor {
+                        name,
+                        email,
+                        commits,
+                    });
+                }
+                "published" => restored.published = Some(value),
+                "updated" => restored.updated = Some(value),
+                "sitemap" => restored.sitemap = value.parse().ok(),
+                "issues_url" => restored.issues_url = Some(parse_link(&value)),
+                "releases_url" => restored.releases_url = Some(parse_link(&value)),
+                "docs_url" => restored.docs_url = Some(value),
+                "contributors_path" => restored.contributors_path = Some(value),
+                _ => {}
+            }
+        }
+        key_matches.then_some(restored)
+    }
+
+    /// Writes the manifest for a freshly processed project.
+    ///
+    /// # Arguments
+    /// * `project` - The processed project
+    /// * `key` - The key computed before processing
+    /// * `inputs` - Source files read while processing, relative to the source directory
+    pub fn store(&self, project: &Project, key: &str, inputs: &[String]) -> Result<()> {
+        let mut manifest = format!("key {}\n", key);
+        for input in inputs {
+            let hash = file_hash(&Path::new(&project.source_dir).join(input));
+            manifest.push_str(&format!(
+                "input {}\n",
+                escape(&format!("{} {}", hash, input))
+            ));
+        }
+        let fields = [
+            ("github_url", project.github_url.clone()),
+            ("title", project.title.clone()),
+            ("description", project.description.clone()),
+            ("logo", project.logo.clone()),
+            ("social_image", project.social_image.clone()),
+            ("status", project.status.clone()),
+            (
+                "featured",
+                project.featured.map(|featured| featured.to_string()),
+            ),
+            ("order", project.order.map(|order| order.to_string())),
+            ("published", project.published.clone()),
+            ("updated", project.updated.clone()),
+            (
+                "sitemap",
+                project.sitemap.map(|sitemap| sitemap.to_string()),
+            ),
+            ("issues_url", project.issues_url.as_ref().map(link_value)),
+            (
+                "releases_url",
+                project.releases_url.as_ref().map(link_value),
+            ),
+            ("docs_url", project.docs_url.clone()),
+            ("contributors_path", project.contributors_path.clone()),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                manifest.push_str(&format!("{} {}\n", field, escape(&value)));
+            }
+        }
+        for tag in &project.tags {
+            manifest.push_str(&format!("tag {}\n", escape(tag)));
+        }
+        for alias in &project.aliases {
+            manifest.push_str(&format!("alias {}\n", escape(alias)));
+        }
+        for subpage in &project.subpages {
+            manifest.push_str(&format!("subpage {}\n", escape(subpage)));
+        }
+        let mut outputs = Vec::new();
+        list_files(Path::new(&project.output_dir), "", &mut outputs);
+        outputs.sort();
+        for output in outputs {
+            manifest.push_str(&format!("output {}\n", escape(&output)));
+        }
+        for contributor in &project.contributors {
+            let value = format!(
+                "{} {} {}",
+                contributor.commits, contributor.email, contributor.name
+            );
+            manifest.push_str(&format!("contributor {}\n", escape(&value)));
+        }
+
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create cache directory {:?}: {}", self.dir, e))?;
+        fs::write(
+            self.manifest_path(project, "html"),
+            project.content.as_deref().unwrap_or_default(),
+        )?;
+        fs::write(self.manifest_path(project, "manifest"), manifest)?;
+        Ok(())
+    }
+
+    /// Prints which projects were rebuilt and which were reused.
+    pub fn print_summary(&self) {
+        println!(
+            "♻️  Build cache: {} rebuilt, {} reused",
+            self.rebuilt.len(),
+            self.reused.len()
+        );
+        if !self.rebuilt.is_empty() {
+            println!("   rebuilt: {}", self.rebuilt.join(", "));
+        }
+        if !self.reused.is_empty() {
+            println!("   reused: {}", self.reused.join(", "));
+        }
+    }
+
+    /// Path of a project's cache file, named after its output directory.
+    fn manifest_path(&self, project: &Project, extension: &str) -> PathBuf {
+        let name: String = project
+            .output_dir
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{}.{}", name, extension))
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Folds bytes into a 64-bit FNV-1a hash.
+///
+/// Unlike `std`'s hasher, FNV-1a is stable across Rust releases, so manifests
+/// stay valid after a toolchain upgrade.
+fn hash(mut state: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        state ^= *byte as u64;
+        state = state.wrapping_mul(FNV_PRIME);
+    }
+    // Separate consecutive values so ("ab", "c") and ("a", "bc") differ
+    (state ^ 0xff).wrapping_mul(FNV_PRIME)
+}
+
+/// Hashes a file's contents, or returns `MISSING` if it can't be read.
+fn file_hash(path: &Path) -> String {
+    match fs::read(path) {
+        Ok(bytes) => format!("{:016x}", hash(FNV_OFFSET, &bytes)),
+        Err(_) => MISSING.to_string(),
+    }
+}
+
+/// Lists the files under `dir` as `/`-separated paths relative to it.
+fn list_files(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        let path = entry.path();
+        if path.is_dir() {
+            list_files(&path, &format!("{}/", name), files);
+        } else {
+            files.push(name);
+        }
+    }
+}
+
+/// Writes a source section link as a manifest value.
+fn link_value(link: &ForgeLink) -> String {
+    match link {
+        ForgeLink::Derived => "derived".to_string(),
+        ForgeLink::Off => "off".to_string(),
+        ForgeLink::Url(url) => format!("url {}", url),
+    }
+}
+
+/// Reverses `link_value`.
+fn parse_link(value: &str) -> ForgeLink {
+    match value {
+        "derived" => ForgeLink::Derived,
+        "off" => ForgeLink::Off,
+        _ => ForgeLink::Url(value.strip_prefix("url ").unwrap_or(value).to_string()),
+    }
+}
+
+/// Escapes a manifest value so it fits on one line.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Reverses `escape`.
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => text.push('\\'),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_hash_is_stable() {
+        assert_eq!(hash(FNV_OFFSET, b"site"), 0x4884_d94e_dcf8_7f23);
+        assert_ne!(
+            hash(hash(FNV_OFFSET, b"ab"), b"c"),
+            hash(hash(FNV_OFFSET, b"a"), b"bc")
+        );
+    }
+
+    #[test]
+    fn test_escape_roundtrip() {
+        let value = "line\nbreak \\n literal";
+        assert!(!escape(value).contains('\n'));
+        assert_eq!(unescape(&escape(value)), value);
+    }
+
+    #[test]
+    fn test_restore_tracks_inputs() {
+        let root = TempDir::new("cache");
+        let source = root.join("source");
+        let output = root.join("output");
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(&output).unwrap();
+        fs::write(source.join("README.md"), "# Demo\n").unwrap();
+        fs::write(output.join("index.html"), "<p>page</p>").unwrap();
+
+        let configured = Project::new(
+            source.to_string_lossy().into_owned(),
+            output.to_string_lossy().into_owned(),
+        )
+        .with_status("active");
+        let site = Site::new("Demo", "https://example.com", "");
+        let cache = BuildCache::new(root.join("cache"), false, &site, "<main></main>", &[]);
+        let key = cache.key(&configured);
+        assert!(cache.restore(&configured, &key).is_none());
+
+        let mut processed = configured.clone();
+        processed.title = Some("Demo\nTitle".to_string());
+        processed.tags = vec!["rust".to_string()];
+        processed.aliases = vec!["/demo/".to_string()];
+        processed.issues_url = Some(ForgeLink::Derived);
+        processed.releases_url = Some(ForgeLink::Url("https://example.com/off".to_string()));
+        processed.content = Some("<h1>Demo</h1>".to_string());
+        cache
+            .store(&processed, &key, &["README.md".to_string()])
+            .unwrap();
+
+        let restored = cache.restore(&configured, &key).unwrap();
+        assert_eq!(restored.title.as_deref(), Some("Demo\nTitle"));
+        assert_eq!(restored.status.as_deref(), Some("active"));
+        assert_eq!(restored.tags, vec!["rust"]);
+        assert_eq!(restored.aliases, vec!["/demo/"]);
+        assert_eq!(restored.issues_url, Some(ForgeLink::Derived));
+        assert_eq!(
+            restored.releases_url,
+            Some(ForgeLink::Url("https://example.com/off".to_string()))
+        );
+        assert_eq!(restored.content.as_deref(), Some("<h1>Demo</h1>"));
+
+        // Every output file recorded in the manifest must still exist
+        fs::create_dir_all(output.join("releases")).unwrap();
+        fs::write(output.join("releases/index.html"), "<p>releases</p>").unwrap();
+        fs::write(output.join("social-card.png"), "").unwrap();
+        cache
+            .store(&processed, &key, &["README.md".to_string()])
+            .unwrap();
+        assert!(cache.restore(&configured, &key).is_some());
+        fs::remove_file(output.join("releases/index.html")).unwrap();
+        assert!(cache.restore(&configured, &key).is_none());
+        fs::write(output.join("releases/index.html"), "<p>releases</p>").unwrap();
+        fs::remove_file(output.join("social-card.png")).unwrap();
+        assert!(cache.restore(&configured, &key).is_none());
+        fs::write(output.join("social-card.png"), "").unwrap();
+
+        // A different layout or site, a forced build or an edited README all invalidate the entry
+        let relaid = BuildCache::new(root.join("cache"), false, &site, "<body></body>", &[]);
+        assert!(
+            relaid
+                .restore(&configured, &relaid.key(&configured))
+                .is_none()
+        );
+        let renamed = Site::new("Renamed", "https://example.com", "");
+        let moved = BuildCache::new(root.join("cache"), false, &renamed, "<main></main>", &[]);
+        assert_ne!(moved.key(&configured), key);
+        let forced = BuildCache::new(root.join("cache"), true, &site, "<main></main>", &[]);
+        assert!(forced.restore(&configured, &key).is_none());
+        fs::write(source.join("README.md"), "# Demo 2\n").unwrap();
+        assert!(cache.restore(&configured, &key).is_none());
+    }
+}
@@ -0,0 +1,420 @@
+//! Link checker for the generated site.
+//!
+//! Crawls every HTML page in the output directory and verifies that internal
+//! `href`/`src` targets exist, that fragment anchors point at an element with a
+//! matching `id`, and optionally that external links are known to be good.
+//! External checks never touch the network: they consult an allowlist of URL
+//! prefixes and a cache of previously recorded HTTP status codes.
+
+use crate::url::percent_decode;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// File of external URL prefixes that are always considered valid.
+pub const ALLOWLIST_FILE: &str = "link-allowlist.txt";
+/// File of `<status> <url>` lines recording previously checked external links.
+pub const CACHE_FILE: &str = "link-cache.txt";
+
+/// The kind of problem found for a link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueKind {
+    /// An internal link whose target file does not exist
+    BrokenLink,
+    /// A fragment that does not match any `id` on the target page
+    MissingAnchor,
+    /// An external link recorded as failing in the cache
+    BrokenExternal,
+    /// An external link that is neither allowlisted nor cached
+    UncheckedExternal,
+}
+
+impl IssueKind {
+    /// Whether this issue fails the check; unchecked external links are only warnings.
+    pub fn is_error(self) -> bool {
+        self != IssueKind::UncheckedExternal
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            IssueKind::BrokenLink => "✗ broken link",
+            IssueKind::MissingAnchor => "✗ missing anchor",
+            IssueKind::BrokenExternal => "✗ broken external link",
+            IssueKind::UncheckedExternal => "? unchecked external link",
+        }
+    }
+}
+
+/// A single problem found on a page.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Issue {
+    pub kind: IssueKind,
+    /// The link as written in the page
+    pub target: String,
+}
+
+/// The result of checking the whole site, grouped by source page.
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    /// Issues keyed by page path, sorted
+    pub pages: BTreeMap<String, Vec<Issue>>,
+    /// Number of pages crawled
+    pub page_count: usize,
+    /// Number of links inspected
+    pub link_count: usize,
+}
+
+impl CheckReport {
+    /// Counts issues that fail the check.
+    pub fn error_count(&self) -> usize {
+        self.pages
+            .values()
+            .flatten()
+            .filter(|issue| issue.kind.is_error())
+            .count()
+    }
+
+    /// Counts issues that are reported as warnings only.
+    pub fn warning_count(&self) -> usize {
+        self.pages
+            .values()
+            .flatten()
+            .filter(|issue| !issue.kind.is_error())
+            .count()
+    }
+
+    /// Prints the report grouped by source page.
+    pub fn print(&self) {
+        println!(
+            "🔗 Checked {} links across {} pages",
+            self.link_count, self.page_count
+        );
+        for (page, issues) in &self.pages {
+            println!("{}", page);
+            for issue in issues {
+                println!("  {}: {}", issue.kind.label(), issue.target);
+            }
+        }
+        println!(
+            "{} errors, {} warnings",
+            self.error_count(),
+            self.warning_count()
+        );
+    }
+}
+
+/// Known-good and known-bad external links, loaded from local files.
+#[derive(Debug, Default)]
+pub struct ExternalLinks {
+    allowlist: Vec<String>,
+    cache: HashMap<String, u16>,
+}
+
+impl ExternalLinks {
+    /// Loads the allowlist and cache files; missing files are treated as empty.
+    ///
+    /// # Arguments
+    /// * `allowlist_path` - File of URL prefixes, one per line; `#` starts a comment
+    /// * `cache_path` - File of `<status> <url>` lines
+    ///
+    /// # Returns
+    /// * `ExternalLinks` - The loaded links
+    pub fn load(allowlist_path: &str, cache_path: &str) -> Self {
+        let lines = |path: &str| -> Vec<String> {
+            fs::read_to_string(path)
+                .unwrap_or_default()
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        };
+
+        let cache = lines(cache_path)
+            .iter()
+            .filter_map(|line| {
+                let (status, url) = line.split_once(char::is_whitespace)?;
+                Some((url.trim().to_string(), status.parse().ok()?))
+            })
+            .collect();
+
+        Self {
+            allowlist: lines(allowlist_path),
+            cache,
+        }
+    }
+
+    /// Classifies an external URL, returning an issue kind if it is not known to be good.
+    fn classify(&self, url: &str) -> Option<IssueKind> {
+        if self.allowlist.iter().any(|prefix| url.starts_with(prefix)) {
+            return None;
+        }
+        match self.cache.get(url) {
+            Some(status) if (200..400).contains(status) => None,
+            Some(_) => Some(IssueKind::BrokenExternal),
+            None => Some(IssueKind::UncheckedExternal),
+        }
+    }
+}
+
+/// Checks every HTML page under `output_dir`.
+///
+/// # Arguments
+/// * `output_dir` - The generated site directory, e.g. `docs`
+/// * `base_url` - The site's absolute base URL; links starting with it are treated as internal
+/// * `external` - Known external links, or None to skip external links entirely
+///
+/// # Returns
+/// * `Result<CheckReport>` - The report, or an error if the output cannot be read
+pub fn check_site(
+    output_dir: &str,
+    base_url: &str,
+    external: Option<&ExternalLinks>,
+) -> Result<CheckReport> {
+    let root = Path::new(output_dir);
+    let mut pages = Vec::new();
+    collect_html_files(root, &mut pages)?;
+    pages.sort();
+
+    // Collect anchors of every page up front so cross-page fragments can be checked
+    let mut anchors: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+    let mut sources: Vec<(PathBuf, String)> = Vec::new();
+    for page in pages {
+        let html = fs::read_to_string(&page)
+            .map_err(|e| format!("Failed to read {}: {}", page.display(), e))?;
+        anchors.insert(page.clone(), extract_anchors(&html));
+        sources.push((page, html));
+    }
+
+    let link_regex = Regex::new(r#"\s(?:href|src)\s*=\s*["']([^"']*)["']"#).unwrap();
+    let mut report = CheckReport {
+        page_count: sources.len(),
+        ..CheckReport::default()
+    };
+
+    for (page, html) in &sources {
+        let mut issues: Vec<Issue> = Vec::new();
+        for caps in link_regex.captures_iter(html) {
+            let link = &caps[1];
+            report.link_count += 1;
+            if let Some(kind) = check_link(root, page, link, base_url, &anchors, external) {
+                issues.push(Issue {
+                    kind,
+                    target: link.to_string(),
+                });
+            }
+        }
+
+        issues.sort();
+        issues.dedup();
+        if !issues.is_empty() {
+            report.pages.insert(page.display().to_string(), issues);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Checks a single link, returning the problem if there is one.
+fn check_link(
+    root: &Path,
+    page: &Path,
+    link: &str,
+    base_url: &str,
+    anchors: &HashMap<PathBuf, HashSet<String>>,
+    external: Option<&ExternalLinks>,
+) -> Option<IssueKind> {
+    let link = link.trim();
+    if link.is_empty()
+        || ["mailto:", "tel:", "javascript:", "data:"]
+            .iter()
+            .any(|scheme| link.starts_with(scheme))
+    {
+        return None;
+    }
+
+    // Absolute links to our own domain are checked like internal ones
+    let link = match link.strip_prefix(base_url) {
+        Some(path) if path.is_empty() || path.starts_with('/') || path.starts_with('#') => {
+            if path.is_empty() { "/" } else { path }
+        }
+        _ => link,
+    };
+    if link.starts_with("http://") || link.starts_with("https://") || link.starts_with("//") {
+        return external.and_then(|links| links.classify(link));
+    }
+
+    let (path, fragment) = match link.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (link, None),
+    };
+    let path = path.split('?').next().unwrap_or_default();
+
+    let target = if path.is_empty() {
+        page.to_path_buf()
+    } else {
+        let Some(decoded) = percent_decode(path) else {
+            return Some(IssueKind::BrokenLink);
+        };
+        let joined = match decoded.strip_prefix('/') {
+            Some(absolute) => root.join(absolute),
+            None => page.parent().unwrap_or(root).join(&decoded),
+        };
+        let normalized = normalize(&joined);
+        if normalized.is_dir() {
+            normalized.join("index.html")
+        } else {
+            normalized
+        }
+    };
+
+    if !target.is_file() {
+        return Some(IssueKind::BrokenLink);
+    }
+
+    match (fragment, anchors.get(&target)) {
+        (Some(fragment), Some(ids)) if !fragment.is_empty() && !ids.contains(fragment) => {
+            Some(IssueKind::MissingAnchor)
+        }
+        _ => None,
+    }
+}
+
+/// Extracts every `id` and `name` attribute value from a page.
+fn extract_anchors(html: &str) -> HashSet<String> {
+    let re = Regex::new(r#"\s(?:id|name)\s*=\s*["']([^"']+)["']"#).unwrap();
+    re.captures_iter(html)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Recursively collects `.html` files under `dir`.
+pub fn collect_html_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_html_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "html") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Resolves `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_check_site() {
+        let root = TempDir::new("check");
+        write(
+            &root.join("index.html"),
+            r##"<a href="/projects/a/#usage">ok</a>
+<a href="/projects/a#missing">bad anchor</a>
+<a href="/missing/">broken</a>
+<a href="https://example.com/projects/a/">own domain</a>
+<a href="https://github.com/x">allowed</a>
+<a href="https://bad.example/">cached broken</a>
+<a href="https://new.example/">unchecked</a>
+<a href="mailto:hi@example.com">mail</a>
+<h2 id="top">Top</h2><a href="#top">self</a>"##,
+        );
+        write(
+            &root.join("projects/a/index.html"),
+            r#"<h2 id="usage">Usage</h2><img src="./logo%20one.png"><img src="../../nope.png"><a href="./bad%zz">bad</a>"#,
+        );
+        write(&root.join("projects/a/logo one.png"), "");
+
+        let external = ExternalLinks {
+            allowlist: vec!["https://github.com/".to_string()],
+            cache: HashMap::from([("https://bad.example/".to_string(), 404)]),
+        };
+        let report = check_site(
+            root.to_str().unwrap(),
+            "https://example.com",
+            Some(&external),
+        )
+        .unwrap();
+
+        let index = &report.pages[&root.join("index.html").display().to_string()];
+        assert_eq!(
+            index,
+            &vec![
+                Issue {
+                    kind: IssueKind::BrokenLink,
+                    target: "/missing/".to_string()
+                },
+                Issue {
+                    kind: IssueKind::MissingAnchor,
+                    target: "/projects/a#missing".to_string()
+                },
+                Issue {
+                    kind: IssueKind::BrokenExternal,
+                    target: "https://bad.example/".to_string()
+                },
+                Issue {
+                    kind: IssueKind::UncheckedExternal,
+                    target: "https://new.example/".to_string()
+                },
+            ]
+        );
+        let project = &report.pages[&root.join("projects/a/index.html").display().to_string()];
+        let targets: Vec<&str> = project.iter().map(|issue| issue.target.as_str()).collect();
+        assert_eq!(targets.len(), 2);
+        assert!(targets.contains(&"../../nope.png") && targets.contains(&"./bad%zz"));
+        assert_eq!(report.error_count(), 5);
+        assert_eq!(report.warning_count(), 1);
+
+        // Offline by default: external links are ignored without a link list
+        let report = check_site(root.to_str().unwrap(), "https://example.com", None).unwrap();
+        assert_eq!(report.error_count(), 4);
+        assert_eq!(report.warning_count(), 0);
+    }
+
+    #[test]
+    fn test_external_links_load() {
+        let dir = TempDir::new("links");
+        let allowlist = dir.join("allow.txt");
+        let cache = dir.join("cache.txt");
+        fs::write(&allowlist, "# comment\nhttps://github.com/\n").unwrap();
+        fs::write(
+            &cache,
+            "200 https://ok.example/\n500 https://down.example/\n",
+        )
+        .unwrap();
+
+        let links = ExternalLinks::load(allowlist.to_str().unwrap(), cache.to_str().unwrap());
+        assert_eq!(links.classify("https://github.com/user/repo"), None);
+        assert_eq!(links.classify("https://ok.example/"), None);
+        assert_eq!(
+            links.classify("https://down.example/"),
+            Some(IssueKind::BrokenExternal)
+        );
+    }
+}
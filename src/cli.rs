@@ -0,0 +1,159 @@
+//! Command-line argument parsing.
+
+use crate::pool;
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Usage text printed for `--help` and argument errors.
+pub const USAGE: &str = "Usage: site [COMMAND] [OPTIONS]
+
+Commands:
+  build         Generate the site into docs/ (default)
+  check         Check links in the generated site
+  serve         Build a preview with drafts and serve it on http://127.0.0.1:8000/
+
+Options:
+  --force       (build) Rebuild every project, ignoring .site-cache/
+  --update      (build) Fetch git sources and move them to the latest commit of their
+                revision, rewriting sources.lock
+  --locked      (build) Fail instead of resolving git sources missing from sources.lock
+  -j, --jobs N  (build) Number of parallel jobs, defaults to the number of CPUs
+  --drafts      (build) Build a preview into .site-cache/preview/ with draft and scheduled
+                pages, marked with a banner
+  --external    (check) Also check external links against link-allowlist.txt and link-cache.txt
+  -h, --help    Print this help";
+
+/// The subcommand to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Generate the site
+    Build,
+    /// Check links in the generated site
+    Check,
+    /// Build including drafts and serve the output locally
+    Serve,
+    /// Print usage and exit
+    Help,
+}
+
+/// Parsed command-line options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The subcommand to run
+    pub command: Command,
+    /// Whether `build` ignores the build cache
+    pub force: bool,
+    /// Whether `build` fetches git sources instead of using sources.lock
+    pub update: bool,
+    /// Whether `build` fails on git sources missing from sources.lock
+    pub locked: bool,
+    /// Number of parallel build jobs
+    pub jobs: usize,
+    /// Whether `build` includes draft and scheduled pages
+    pub drafts: bool,
+    /// Whether `check` also verifies external links
+    pub external: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            command: Command::Build,
+            force: false,
+            update: false,
+            locked: false,
+            jobs: pool::default_jobs(),
+            drafts: false,
+            external: false,
+        }
+    }
+}
+
+/// Parses command-line arguments, excluding the program name.
+///
+/// # Arguments
+/// * `args` - The arguments, e.g. `std::env::args().skip(1)`
+///
+/// # Returns
+/// * `Result<Options>` - The parsed options, or an error naming the unknown argument
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options> {
+    let mut options = Options::default();
+    let mut args = args.into_iter().enumerate();
+    while let Some((position, arg)) = args.next() {
+        match arg.as_str() {
+            "build" if position == 0 => options.command = Command::Build,
+            "check" if position == 0 => options.command = Command::Check,
+            "serve" if position == 0 => options.command = Command::Serve,
+            "--force" => options.force = true,
+            "--update" => options.update = true,
+            "--locked" => options.locked = true,
+            "-j" | "--jobs" => {
+                options.jobs = args
+                    .next()
+                    .and_then(|(_, jobs)| jobs.parse().ok())
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or_else(|| format!("{} expects a positive number\n\n{}", arg, USAGE))?;
+            }
+            "--drafts" => options.drafts = true,
+            "--external" => options.external = true,
+            "-h" | "--help" => options.command = Command::Help,
+            other => return Err(format!("Unknown argument: {}\n\n{}", other, USAGE).into()),
+        }
+    }
+    if options.update && options.locked {
+        return Err(format!("--update and --locked can't be combined\n\n{}", USAGE).into());
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_defaults_to_build() {
+        assert_eq!(parse(args(&[])).unwrap(), Options::default());
+    }
+
+    #[test]
+    fn test_parse_check_external() {
+        let options = parse(args(&["check", "--external"])).unwrap();
+        assert_eq!(options.command, Command::Check);
+        assert!(options.external);
+    }
+
+    #[test]
+    fn test_parse_force() {
+        let options = parse(args(&["build", "--force"])).unwrap();
+        assert_eq!(options.command, Command::Build);
+        assert!(options.force);
+        assert!(parse(args(&["--update"])).unwrap().update);
+        assert!(parse(args(&["--locked"])).unwrap().locked);
+        assert!(parse(args(&["--update", "--locked"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_jobs() {
+        assert_eq!(parse(args(&["--jobs", "3"])).unwrap().jobs, 3);
+        assert_eq!(parse(args(&["build", "-j", "1"])).unwrap().jobs, 1);
+        assert!(parse(args(&["--jobs"])).is_err());
+        assert!(parse(args(&["--jobs", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_serve_and_drafts() {
+        assert_eq!(parse(args(&["serve"])).unwrap().command, Command::Serve);
+        assert!(parse(args(&["build", "--drafts"])).unwrap().drafts);
+    }
+
+    #[test]
+    fn test_parse_unknown_argument() {
+        assert!(parse(args(&["--frobnicate"])).is_err());
+        assert!(parse(args(&["build", "check"])).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Timestamp parsing and formatting.
+//!
+//! Dates travel through the build as RFC 3339 strings with the offset they
+//! were recorded in, as Git stores them. They are compared as Unix seconds and
+//! converted with the civil-from-days algorithm, so no date library is needed.
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// A point in time plus the UTC offset it was written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    /// Seconds since 1970-01-01T00:00:00Z
+    pub seconds: i64,
+    /// Offset from UTC in minutes, e.g. `120` for `+02:00`
+    pub offset_minutes: i64,
+}
+
+impl Timestamp {
+    /// Creates a timestamp from Unix seconds and an offset in minutes.
+    pub fn new(seconds: i64, offset_minutes: i64) -> Self {
+        Self {
+            seconds,
+            offset_minutes,
+        }
+    }
+
+    /// Parses an RFC 3339 timestamp such as `2026-10-01T09:30:00+02:00`.
+    ///
+    /// Fractional seconds are ignored and `Z` means UTC.
+    ///
+    /// # Arguments
+    /// * `date` - The timestamp
+    ///
+    /// # Returns
+    /// * `Option<Timestamp>` - The timestamp, or None if it isn't valid RFC 3339
+    pub fn parse_rfc3339(date: &str) -> Option<Self> {
+        let number = |range: std::ops::Range<usize>| -> Option<i64> {
+            let digits = date.get(range)?;
+            digits
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| digits.parse().ok())?
+        };
+        let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+        if separators
+            .iter()
+            .any(|&(index, byte)| date.as_bytes().get(index) != Some(&byte))
+            || !matches!(date.as_bytes().get(10), Some(b'T' | b't' | b' '))
+        {
+            return None;
+        }
+
+        let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+        let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+        if !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 60
+        {
+            return None;
+        }
+
+        let mut zone = date.get(19..)?;
+        if let Some(fraction) = zone.strip_prefix('.') {
+            zone = fraction.trim_start_matches(|c: char| c.is_ascii_digit());
+        }
+        let offset_minutes = match zone {
+            "Z" | "z" => 0,
+            _ => {
+                let sign = match zone.get(..1)? {
+                    "+" => 1,
+                    "-" => -1,
+                    _ => return None,
+                };
+                if zone.len() != 6 || zone.as_bytes()[3] != b':' {
+                    return None;
+                }
+                let hours: i64 = zone.get(1..3)?.parse().ok()?;
+                let minutes: i64 = zone.get(4..6)?.parse().ok()?;
+                sign * (hours * 60 + minutes)
+            }
+        };
+
+        let local = days_from_civil(year, month, day) * SECONDS_PER_DAY
+            + hour * 3600
+            + minute * 60
+            + second;
+        Some(Self::new(local - offset_minutes * 60, offset_minutes))
+    }
+
+    /// Splits the local time into a civil date and seconds since midnight.
+    fn local(self) -> ((i64, i64, i64), i64) {
+        let local = self.seconds + self.offset_minutes * 60;
+        (
+            civil_from_days(local.div_euclid(SECONDS_PER_DAY)),
+            local.rem_euclid(SECONDS_PER_DAY),
+        )
+    }
+
+    /// Formats the timestamp in RFC 3339 format with its offset, e.g.
+    /// `2026-10-01T09:30:00+02:00`.
+    pub fn to_rfc3339(self) -> String {
+        let ((year, month, day), seconds) = self.local();
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60,
+            self.offset(":")
+        )
+    }
+
+    /// Formats the timestamp in the RFC 2822 format RSS requires, e.g.
+    /// `Thu, 01 Oct 2026 09:30:00 +0200`.
+    pub fn to_rfc2822(self) -> String {
+        const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+
+        let ((year, month, day), seconds) = self.local();
+        // 1970-01-01 was a Thursday
+        let days = (self.seconds + self.offset_minutes * 60).div_euclid(SECONDS_PER_DAY);
+        format!(
+            "{}, {:02} {} {} {:02}:{:02}:{:02} {}",
+            DAYS[(days + 3).rem_euclid(7) as usize],
+            day,
+            MONTHS[month as usize - 1],
+            year,
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60,
+            self.offset("")
+        )
+    }
+
+    /// Formats the offset as `+HH<separator>MM`.
+    fn offset(self, separator: &str) -> String {
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        format!(
+            "{}{:02}{}{:02}",
+            sign,
+            self.offset_minutes.abs() / 60,
+            separator,
+            self.offset_minutes.abs() % 60
+        )
+    }
+}
+
+/// Converts days since 1970-01-01 to a `(year, month, day)` civil date.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// Converts a civil date to days since 1970-01-01, the inverse of `civil_from_days`.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc3339_roundtrip() {
+        assert_eq!(
+            Timestamp::new(0, 0).to_rfc3339(),
+            "1970-01-01T00:00:00+00:00"
+        );
+        for date in [
+            "2024-02-29T23:59:59-05:00",
+            "1969-12-31T23:59:59+00:00",
+            "2026-10-01T09:30:00+05:45",
+        ] {
+            assert_eq!(Timestamp::parse_rfc3339(date).unwrap().to_rfc3339(), date);
+        }
+        assert_eq!(
+            Timestamp::parse_rfc3339("2026-10-01T07:30:00.250Z"),
+            Timestamp::parse_rfc3339("2026-10-01T09:30:00+02:00")
+                .map(|t| Timestamp::new(t.seconds, 0))
+        );
+        assert_eq!(Timestamp::parse_rfc3339("not a date"), None);
+        assert_eq!(Timestamp::parse_rfc3339("2026-13-01T00:00:00Z"), None);
+        assert_eq!(Timestamp::parse_rfc3339("2026-10-01T00:00:00"), None);
+    }
+
+    #[test]
+    fn test_rfc2822() {
+        let format = |date| Timestamp::parse_rfc3339(date).unwrap().to_rfc2822();
+        assert_eq!(
+            format("1970-01-01T00:00:00Z"),
+            "Thu, 01 Jan 1970 00:00:00 +0000"
+        );
+        assert_eq!(
+            format("2024-02-29T23:59:59-05:00"),
+            "Thu, 29 Feb 2024 23:59:59 -0500"
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Atom and RSS feed generation for project and experiment updates.
+
+use crate::date::Timestamp;
+use crate::html;
+use crate::project::Project;
+use crate::site::Site;
+
+/// A single feed entry.
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    /// Entry title
+    pub title: String,
+    /// Absolute URL of the page, also used as the entry ID
+    pub url: String,
+    /// First publication date in RFC 3339 format
+    pub published: String,
+    /// Last update date in RFC 3339 format
+    pub updated: String,
+    /// Short plain-text summary
+    pub summary: Option<String>,
+    /// Full HTML content, omitted for summary-only feeds
+    pub content: Option<String>,
+}
+
+impl FeedEntry {
+    /// Builds a feed entry from a processed project.
+    ///
+    /// # Arguments
+    /// * `project` - The processed project
+    /// * `site` - The site settings
+    /// * `full_content` - Whether to include the full page HTML
+    ///
+    /// # Returns
+    /// * `Option<FeedEntry>` - The entry, or None if the project has no commit dates
+    pub fn from_project(project: &Project, site: &Site, full_content: bool) -> Option<Self> {
+        Some(Self {
+            title: project.display_title().to_string(),
+            url: site.absolute_url(&project.url()),
+            published: project.published.clone()?,
+            updated: project.updated.clone()?,
+            summary: project.description.clone(),
+            content: if full_content {
+                project.content.clone()
+            } else {
+                None
+            },
+        })
+    }
+}
+
+/// Sorts entries newest first by update date, compared in UTC.
+///
+/// Entries whose date doesn't parse go last.
+fn sorted(entries: &[FeedEntry]) -> Vec<&FeedEntry> {
+    let mut sorted: Vec<&FeedEntry> = entries.iter().collect();
+    sorted.sort_by_cached_key(|entry| {
+        std::cmp::Reverse(Timestamp::parse_rfc3339(&entry.updated).map(|date| date.seconds))
+    });
+    sorted
+}
+
+/// Creates an Atom 1.0 feed document.
+///
+/// Relative links inside entry content are resolved against the entry URL via `xml:base`.
+///
+/// # Arguments
+/// * `site` - The site settings
+/// * `feed_url` - Absolute URL the feed is published at
+/// * `entries` - The feed entries in any order
+///
+/// # Returns
+/// * `String` - The Atom XML
+pub fn create_atom_feed(site: &Site, feed_url: &str, entries: &[FeedEntry]) -> String {
+    let entries = sorted(entries);
+    let updated = entries
+        .first()
+        .map(|entry| entry.updated.as_str())
+        .unwrap_or("1970-01-01T00:00:00Z");
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <title>{}</title>\n", html::escape(&site.name)));
+    xml.push_str(&format!(
+        "  <subtitle>{}</subtitle>\n",
+        html::escape(&site.description)
+    ));
+    xml.push_str(&format!(
+        "  <link href=\"{}\" rel=\"self\" type=\"application/atom+xml\"/>\n",
+        html::escape(feed_url)
+    ));
+    xml.push_str(&format!(
+        "  <link href=\"{}\"/>\n",
+        html::escape(&site.absolute_url("/"))
+    ));
+    xml.push_str(&format!(
+        "  <id>{}</id>\n",
+        html::escape(&site.absolute_url("/"))
+    ));
+    xml.push_str(&format!("  <updated>{}</updated>\n", updated));
+    xml.push_str(&format!(
+        "  <author><name>{}</name></author>\n",
+        html::escape(&site.name)
+    ));
+
+    for entry in entries {
+        let url = html::escape(&entry.url);
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!(
+            "    <title>{}</title>\n",
+            html::escape(&entry.title)
+        ));
+        xml.push_str(&format!("    <link href=\"{}\"/>\n", url));
+        xml.push_str(&format!("    <id>{}</id>\n", url));
+        xml.push_str(&format!(
+            "    <published>{}</published>\n",
+            html::escape(&entry.published)
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            html::escape(&entry.updated)
+        ));
+        if let Some(summary) = &entry.summary {
+            xml.push_str(&format!(
+                "    <summary>{}</summary>\n",
+                html::escape(summary)
+            ));
+        }
+        if let Some(content) = &entry.content {
+            xml.push_str(&format!(
+                "    <content type=\"html\" xml:base=\"{}\">{}</content>\n",
+                url,
+                html::escape(content)
+            ));
+        }
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// Creates an RSS 2.0 feed document.
+///
+/// # Arguments
+/// * `site` - The site settings
+/// * `feed_url` - Absolute URL the feed is published at
+/// * `entries` - The feed entries in any order
+///
+/// # Returns
+/// * `String` - The RSS XML
+pub fn create_rss_feed(site: &Site, feed_url: &str, entries: &[FeedEntry]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n  <channel>\n");
+    xml.push_str(&format!(
+        "    <title>{}</title>\n",
+        html::escape(&site.name)
+    ));
+    xml.push_str(&format!(
+        "    <link>{}</link>\n",
+        html::escape(&site.absolute_url("/"))
+    ));
+    xml.push_str(&format!(
+        "    <description>{}</description>\n",
+        html::escape(&site.description)
+    ));
+    xml.push_str(&format!(
+        "    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        html::escape(feed_url)
+    ));
+
+    for entry in sorted(entries) {
+        let url = html::escape(&entry.url);
+        xml.push_str("    <item>\n");
+        xml.push_str(&format!(
+            "      <title>{}</title>\n",
+            html::escape(&entry.title)
+        ));
+        xml.push_str(&format!("      <link>{}</link>\n", url));
+        xml.push_str(&format!(
+            "      <guid isPermaLink=\"true\">{}</guid>\n",
+            url
+        ));
+        if let Some(date) = Timestamp::parse_rfc3339(&entry.updated) {
+            xml.push_str(&format!("      <pubDate>{}</pubDate>\n", date.to_rfc2822()));
+        }
+        if let Some(description) = entry.content.as_ref().or(entry.summary.as_ref()) {
+            xml.push_str(&format!(
+                "      <description>{}</description>\n",
+                html::escape(description)
+            ));
+        }
+        xml.push_str("    </item>\n");
+    }
+
+    xml.push_str("  </channel>\n</rss>\n");
+    xml
+}
+
+/// Creates the `<link rel="alternate">` tags used for feed autodiscovery.
+///
+/// # Arguments
+/// * `site` - The site settings
+/// * `rss` - Whether an RSS feed is published alongside the Atom feed
+///
+/// # Returns
+/// * `String` - HTML link tags for the layout's `<head>`
+pub fn create_feed_links(site: &Site, rss: bool) -> String {
+    let title = html::escape(&site.name);
+    let mut links = format!(
+        "<link rel=\"alternate\" type=\"application/atom+xml\" title=\"{}\" href=\"/atom.xml\" />",
+        title
+    );
+    if rss {
+        links.push_str(&format!(
+            "\n        <link rel=\"alternate\" type=\"application/rss+xml\" title=\"{}\" href=\"/rss.xml\" />",
+            title
+        ));
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<FeedEntry> {
+        vec![
+            FeedEntry {
+                title: "Older".to_string(),
+                url: "https://example.com/older/".to_string(),
+                published: "2025-01-01T00:00:00Z".to_string(),
+                updated: "2025-02-01T00:00:00Z".to_string(),
+                summary: Some("Old & dusty".to_string()),
+                content: None,
+            },
+            FeedEntry {
+                title: "Newer".to_string(),
+                url: "https://example.com/newer/".to_string(),
+                published: "2026-09-01T10:00:00+02:00".to_string(),
+                updated: "2026-10-01T09:30:00+02:00".to_string(),
+                summary: None,
+                content: Some("<p><img src=\"./a.png\"></p>".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_create_atom_feed() {
+        let site = Site::new("Labs", "https://example.com", "Things");
+        let xml = create_atom_feed(&site, "https://example.com/atom.xml", &entries());
+        assert!(xml.contains("<updated>2026-10-01T09:30:00+02:00</updated>\n  <author>"));
+        assert!(xml.find("<title>Newer</title>") < xml.find("<title>Older</title>"));
+        assert!(xml.contains("<summary>Old &amp; dusty</summary>"));
+        assert!(xml.contains(
+            "<content type=\"html\" xml:base=\"https://example.com/newer/\">&lt;p&gt;&lt;img src=&quot;./a.png&quot;&gt;&lt;/p&gt;</content>"
+        ));
+    }
+
+    #[test]
+    fn test_create_rss_feed() {
+        let site = Site::new("Labs", "https://example.com", "Things");
+        let xml = create_rss_feed(&site, "https://example.com/rss.xml", &entries());
+        assert!(xml.contains("<pubDate>Thu, 01 Oct 2026 09:30:00 +0200</pubDate>"));
+        assert!(xml.contains("<description>Old &amp; dusty</description>"));
+    }
+
+    #[test]
+    fn test_sorted_compares_utc() {
+        let mut entries = entries();
+        // Later on the clock, but earlier in UTC than 09:30+02:00
+        entries[0].updated = "2026-10-01T08:00:00+05:00".to_string();
+        let titles: Vec<&str> = sorted(&entries)
+            .iter()
+            .map(|entry| entry.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Newer", "Older"]);
+
+        entries[0].updated = "2026-10-01T08:00:00-05:00".to_string();
+        assert_eq!(sorted(&entries)[0].title, "Older");
+    }
+
+    #[test]
+    fn test_create_feed_links() {
+        let site = Site::new("Labs", "https://example.com", "");
+        assert!(!create_feed_links(&site, false).contains("rss"));
+        assert!(create_feed_links(&site, true).contains("href=\"/rss.xml\""));
+    }
+}
@@ -0,0 +1,203 @@
+//! Front matter parsing for markdown sources.
+//!
+//! Front matter is an optional block at the very top of a markdown file, fenced
+//! by `---` lines, holding simple `key: value` pairs. Values may be plain
+//! scalars, inline lists (`[a, b]`) or block lists (lines starting with `- `).
+//! Only this small subset of YAML is supported; anything else is ignored.
+
+/// A single front matter value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A plain scalar such as `title: Reservoir`
+    Scalar(String),
+    /// A list such as `tags: [rust, llm]`
+    List(Vec<String>),
+}
+
+/// Parsed front matter entries in source order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontMatter {
+    entries: Vec<(String, Value)>,
+}
+
+impl FrontMatter {
+    /// Returns the scalar value for `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find_map(|(k, v)| match v {
+            Value::Scalar(s) if k == key => Some(s.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Returns the value for `key` interpreted as a boolean (`true`/`false`, `yes`/`no`).
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)?.to_ascii_lowercase().as_str() {
+            "true" | "yes" => Some(true),
+            "false" | "no" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Returns the value for `key` parsed as an integer.
+    pub fn get_i32(&self, key: &str) -> Option<i32> {
+        self.get(key)?.parse().ok()
+    }
+
+    /// Returns the list value for `key`. A scalar is treated as a one-element list.
+    pub fn get_list(&self, key: &str) -> Option<Vec<String>> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| match v {
+                Value::Scalar(s) => vec![s.clone()],
+                Value::List(items) => items.clone(),
+            })
+    }
+}
+
+/// Splits a markdown document into its front matter and the remaining body.
+///
+/// # Arguments
+/// * `content` - The full markdown source
+///
+/// # Returns
+/// * `(FrontMatter, &str)` - The parsed front matter (empty if there is none) and the body
+///
+/// # Examples
+/// ```
+/// let (meta, body) = split("---\ntitle: Reservoir\n---\n# Hello");
+/// assert_eq!(meta.get("title"), Some("Reservoir"));
+/// assert_eq!(body, "# Hello");
+/// ```
+pub fn split(content: &str) -> (FrontMatter, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (FrontMatter::default(), content);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let block = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return (parse(block), body);
+        }
+        offset += line.len();
+    }
+
+    // No closing fence: treat the whole document as body
+    (FrontMatter::default(), content)
+}
+
+/// Parses the inside of a front matter block.
+fn parse(block: &str) -> FrontMatter {
+    let mut entries: Vec<(String, Value)> = Vec::new();
+
+    for line in block.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // Block list item belonging to the previous key
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if let Some((_, value)) = entries.last_mut() {
+                let item = unquote(item.trim()).to_string();
+                match value {
+                    Value::List(items) => items.push(item),
+                    Value::Scalar(s) if s.is_empty() => *value = Value::List(vec![item]),
+                    Value::Scalar(_) => {}
+                }
+            }
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            Some(inner) => Value::List(
+                inner
+                    .split(',')
+                    .map(|item| unquote(item.trim()).to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect(),
+            ),
+            None => Value::Scalar(unquote(value).to_string()),
+        };
+        entries.push((key.trim().to_string(), value));
+    }
+
+    FrontMatter { entries }
+}
+
+/// Removes one pair of matching surrounding quotes.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_without_front_matter() {
+        let (meta, body) = split("# Title\n\nBody");
+        assert_eq!(meta, FrontMatter::default());
+        assert_eq!(body, "# Title\n\nBody");
+    }
+
+    #[test]
+    fn test_split_scalars() {
+        let content = "---\ntitle: \"Reservoir\"\nfeatured: true\norder: 2\n---\n# Hello\n";
+        let (meta, body) = split(content);
+        assert_eq!(meta.get("title"), Some("Reservoir"));
+        assert_eq!(meta.get_bool("featured"), Some(true));
+        assert_eq!(meta.get_i32("order"), Some(2));
+        assert_eq!(meta.get_list("title"), Some(vec!["Reservoir".to_string()]));
+        assert_eq!(body, "# Hello\n");
+    }
+
+    #[test]
+    fn test_split_lists() {
+        let content = "---\ntags: [rust, 'llm']\naliases:\n  - /old/\n  - /older/\n---\n";
+        let (meta, _) = split(content);
+        assert_eq!(
+            meta.entries,
+            vec![
+                (
+                    "tags".to_string(),
+                    Value::List(vec!["rust".to_string(), "llm".to_string()])
+                ),
+                (
+                    "aliases".to_string(),
+                    Value::List(vec!["/old/".to_string(), "/older/".to_string()])
+                ),
+            ]
+        );
+        assert_eq!(meta.get("tags"), None);
+        assert_eq!(
+            meta.get_list("aliases"),
+            Some(vec!["/old/".to_string(), "/older/".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_split_unclosed_fence() {
+        let content = "---\ntitle: Oops\n# Body";
+        let (meta, body) = split(content);
+        assert_eq!(meta.get("title"), None);
+        assert_eq!(body, content);
+    }
+}
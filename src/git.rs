@@ -1,16 +1,31 @@
-use std::process::Command;
+//! In-process Git access through libgit2.
+//!
+//! Repositories are opened directly rather than through the `git` binary, so
+//! the builder doesn't need Git installed, and worktrees and submodules (whose
+//! `.git` is a file) as well as bare repositories all work.
+
+use crate::date::Timestamp;
+use crate::forge::RemoteUrl;
+use git2::build::CheckoutBuilder;
+use git2::{Commit, DescribeFormatOptions, DescribeOptions, ObjectType, Oid, Repository};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
 /// Retrieves the Git remote URL for the 'origin' remote in the specified directory.
 ///
-/// This function executes `git remote get-url origin` in the given directory and
-/// processes the output to return a web-friendly URL.
+/// This function reads the `origin` remote from the repository's config and
+/// converts it to the repository's web URL using `forge::RemoteUrl`, dropping
+/// any embedded credentials.
 ///
 /// # Arguments
 /// * `dir` - The directory path where the Git repository is located
 ///
 /// # Returns
-/// * `Some(String)` - The Git remote URL converted to HTTPS format if successful
-/// * `None` - If the Git command fails, directory is not a Git repo, or URL format is unsupported
+/// * `Some(String)` - The repository's web URL if successful
+/// * `None` - If the directory is not a Git repo, has no origin, or the remote is a local path
 ///
 /// # Examples
 /// ```
@@ -18,38 +33,535 @@ use std::process::Command;
 /// assert_eq!(url, Some("https://github.com/user/repo".to_string()));
 /// ```
 pub fn get_git_remote_url(dir: &str) -> Option<String> {
-    let output = Command::new("git")
-        .args(&["remote", "get-url", "origin"])
-        .current_dir(dir)
-        .output()
+    let repo = Repository::open(dir).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    RemoteUrl::parse(remote.url()?).map(|remote| remote.web_url)
+}
+
+/// Checks if a directory is a Git repository.
+///
+/// Recognises regular checkouts, worktrees and submodules (where `.git` is a
+/// file pointing elsewhere) and bare repositories.
+///
+/// # Arguments
+/// * `dir` - The directory path to check
+///
+/// # Returns
+/// * `true` - If the directory is the root of a Git repository
+/// * `false` - Otherwise
+pub fn is_git_repository(dir: &str) -> bool {
+    Repository::open(dir).is_ok()
+}
+
+/// A commit in a file's history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Full commit hash
+    pub id: String,
+    /// Committer date in RFC 3339 format
+    pub date: String,
+}
+
+/// Lists the commits reachable from `HEAD` that changed a path, newest first.
+///
+/// Like `git log -- <path>`, a merge is only listed if it differs from every parent.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+/// * `path` - The file or directory path, relative to `dir`
+///
+/// # Returns
+/// * `Vec<LogEntry>` - The commits, or an empty list if the repository can't be read
+pub fn log(dir: &str, path: &str) -> Vec<LogEntry> {
+    let Ok(repo) = Repository::open(dir) else {
+        return Vec::new();
+    };
+    history(&repo, Some(path))
+        .unwrap_or_default()
+        .iter()
+        .map(|commit| LogEntry {
+            id: commit.id().to_string(),
+            date: format_time(commit.committer().when()),
+        })
+        .collect()
+}
+
+/// Retrieves the dates of the first and last commits that touched a file.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+/// * `path` - The file path, relative to `dir`
+///
+/// # Returns
+/// * `Some((String, String))` - The first and last commit dates in RFC 3339 format
+/// * `None` - If the repository can't be read or the file has no history
+pub fn get_commit_dates(dir: &str, path: &str) -> Option<(String, String)> {
+    let log = log(dir, path);
+    // The log lists newest first
+    Some((log.last()?.date.clone(), log.first()?.date.clone()))
+}
+
+/// Retrieves the hash of the last commit that touched a file.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+/// * `path` - The file path, relative to `dir`
+///
+/// # Returns
+/// * `Option<String>` - The full commit hash, or None if the file has no history
+pub fn get_last_commit(dir: &str, path: &str) -> Option<String> {
+    log(dir, path).into_iter().next().map(|entry| entry.id)
+}
+
+/// Retrieves the most recent tag reachable from the checked-out commit.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+///
+/// # Returns
+/// * `Option<String>` - The tag name, e.g. `v0.4.2`, or None if there are no tags
+pub fn get_latest_tag(dir: &str) -> Option<String> {
+    let repo = Repository::open(dir).ok()?;
+    // Describe the HEAD commit rather than the working tree, which bare repositories lack
+    let head = repo.head().ok()?.peel_to_commit().ok()?;
+    let describe = head
+        .as_object()
+        .describe(DescribeOptions::new().describe_tags())
         .ok()?;
+    describe
+        .format(Some(DescribeFormatOptions::new().abbreviated_size(0)))
+        .ok()
+}
+
+/// Retrieves the repository's default branch.
+///
+/// Uses `origin/HEAD` when the remote advertises one, falling back to the
+/// locally checked-out branch.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+///
+/// # Returns
+/// * `Option<String>` - The branch name, e.g. `main`
+pub fn get_default_branch(dir: &str) -> Option<String> {
+    let repo = Repository::open(dir).ok()?;
+    let remote_head = repo
+        .find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|head| head.symbolic_target().map(str::to_string));
+    if let Some(branch) = remote_head.and_then(|target| {
+        target
+            .strip_prefix("refs/remotes/origin/")
+            .map(str::to_string)
+    }) {
+        return Some(branch);
+    }
+    let head = repo.find_reference("HEAD").ok()?;
+    head.symbolic_target()?
+        .strip_prefix("refs/heads/")
+        .map(str::to_string)
+}
+
+/// Retrieves the commit checked out in a repository.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+///
+/// # Returns
+/// * `Option<String>` - The full commit hash of `HEAD`
+pub fn get_head_commit(dir: &str) -> Option<String> {
+    let repo = Repository::open(dir).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+/// Reads a file as it is at a revision.
+///
+/// Works for bare repositories, which have no files on disk.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+/// * `rev` - The branch, tag or commit, e.g. `HEAD`
+/// * `path` - The file path, relative to the repository root
+///
+/// # Returns
+/// * `Option<Vec<u8>>` - The file contents, or None if it doesn't exist at that revision
+pub fn read_blob(dir: &str, rev: &str, path: &str) -> Option<Vec<u8>> {
+    let repo = Repository::open(dir).ok()?;
+    let tree = repo.revparse_single(rev).ok()?.peel_to_tree().ok()?;
+    let entry = tree.get_path(Path::new(path)).ok()?;
+    let blob = entry.to_object(&repo).ok()?.peel_to_blob().ok()?;
+    Some(blob.content().to_vec())
+}
 
-    if output.status.success() {
-        let url = String::from_utf8(output.stdout).ok()?.trim().to_string();
-
-        // Convert SSH URLs to HTTPS for web links
-        if url.starts_with("git@github.com:") {
-            let repo = url.strip_prefix("git@github.com:")?.strip_suffix(".git")?;
-            Some(format!("https://github.com/{}", repo))
-        } else if url.starts_with("https://github.com/") {
-            Some(url.strip_suffix(".git").unwrap_or(&url).to_string())
-        } else {
-            // Return other URLs as-is (GitLab, Bitbucket, etc.)
-            Some(url.strip_suffix(".git").unwrap_or(&url).to_string())
+/// A tag in a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// Tag name, e.g. `v0.4.2`
+    pub name: String,
+    /// Tagger date for annotated tags, commit date otherwise, in RFC 3339 format
+    pub date: String,
+    /// Tag message subject, or the tagged commit's subject for lightweight tags
+    pub message: String,
+}
+
+/// Lists the repository's tags, newest first.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+///
+/// # Returns
+/// * `Vec<Tag>` - The tags, or an empty list if there are none or the repository can't be read
+pub fn get_tags(dir: &str) -> Vec<Tag> {
+    let Ok(repo) = Repository::open(dir) else {
+        return Vec::new();
+    };
+    let Ok(names) = repo.tag_names(None) else {
+        return Vec::new();
+    };
+
+    let mut tags: Vec<(i64, Tag)> = names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            let object = repo.revparse_single(&format!("refs/tags/{}", name)).ok()?;
+            let (time, message) = match object.as_tag() {
+                Some(tag) => (
+                    tag.tagger()?.when(),
+                    tag.message().unwrap_or_default().to_string(),
+                ),
+                None => {
+                    let commit = object.peel_to_commit().ok()?;
+                    (
+                        commit.committer().when(),
+                        commit.message().unwrap_or_default().to_string(),
+                    )
+                }
+            };
+            Some((
+                time.seconds(),
+                Tag {
+                    name: name.to_string(),
+                    date: format_time(time),
+                    message: message
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                },
+            ))
+        })
+        .collect();
+    tags.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    tags.into_iter().map(|(_, tag)| tag).collect()
+}
+
+/// A person who committed to a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contributor {
+    /// Display name, after `.mailmap` is applied
+    pub name: String,
+    /// Email address, after `.mailmap` is applied; never published
+    pub email: String,
+    /// Number of commits
+    pub commits: usize,
+}
+
+impl Contributor {
+    /// Whether the account looks like an automated bot, e.g. `dependabot[bot]`.
+    pub fn is_bot(&self) -> bool {
+        let name = self.name.to_lowercase();
+        name.ends_with("[bot]")
+            || name.ends_with(" bot")
+            || name.ends_with("-bot")
+            || self.email.to_lowercase().contains("[bot]@")
+    }
+}
+
+/// Lists the people who committed to a repository, most commits first.
+///
+/// Equivalent to `git shortlog -sne HEAD`, so `.mailmap` is respected. Bot
+/// accounts are left out.
+///
+/// # Arguments
+/// * `dir` - The directory path where the Git repository is located
+/// * `path` - Only count commits touching this path, relative to `dir`
+///
+/// # Returns
+/// * `Vec<Contributor>` - The contributors, or an empty list if the repository can't be read
+pub fn get_contributors(dir: &str, path: Option<&str>) -> Vec<Contributor> {
+    let Ok(repo) = Repository::open(dir) else {
+        return Vec::new();
+    };
+    let Ok(commits) = history(&repo, path) else {
+        return Vec::new();
+    };
+    let mailmap = repo.mailmap().ok();
+
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+    for commit in &commits {
+        let author = match &mailmap {
+            Some(mailmap) => commit.author_with_mailmap(mailmap),
+            None => Ok(commit.author()),
+        };
+        if let Ok(author) = author {
+            let key = (
+                author.name().unwrap_or_default().to_string(),
+                author.email().unwrap_or_default().to_string(),
+            );
+            *counts.entry(key).or_default() += 1;
         }
-    } else {
-        None
     }
+
+    let mut contributors: Vec<Contributor> = counts
+        .into_iter()
+        .map(|((name, email), commits)| Contributor {
+            name,
+            email,
+            commits,
+        })
+        .filter(|contributor| !contributor.is_bot())
+        .collect();
+    contributors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    contributors
 }
 
-/// Checks if a directory is a Git repository by checking for the .git directory.
+/// Walks the commits reachable from `HEAD`, newest first, keeping those that changed `path`.
+fn history<'r>(repo: &'r Repository, path: Option<&str>) -> Result<Vec<Commit<'r>>> {
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.set_sorting(git2::Sort::TIME)?;
+
+    let mut commits = Vec::new();
+    for id in walk {
+        let commit = repo.find_commit(id?)?;
+        let touched = match path {
+            Some(path) => changes_path(&commit, Path::new(path))?,
+            None => true,
+        };
+        if touched {
+            commits.push(commit);
+        }
+    }
+    Ok(commits)
+}
+
+/// Whether a commit changed a path compared to each of its parents.
+fn changes_path(commit: &Commit, path: &Path) -> Result<bool> {
+    let entry_id = |commit: &Commit| -> Result<Option<Oid>> {
+        Ok(commit.tree()?.get_path(path).ok().map(|entry| entry.id()))
+    };
+    let id = entry_id(commit)?;
+    if commit.parent_count() == 0 {
+        return Ok(id.is_some());
+    }
+    for parent in commit.parents() {
+        if entry_id(&parent)? == id {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Clones a repository.
 ///
 /// # Arguments
-/// * `dir` - The directory path to check
+/// * `url` - The repository URL or local path
+/// * `dir` - The directory to clone into; its parent is created if needed
+pub fn clone_repository(url: &str, dir: &Path) -> Result<()> {
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Repository::clone(url, dir).map_err(|e| format!("Failed to clone {}: {}", url, e))?;
+    Ok(())
+}
+
+/// Gets the URL a clone's `origin` remote is configured with, as written.
+///
+/// # Arguments
+/// * `dir` - The local clone
 ///
 /// # Returns
-/// * `true` - If the directory contains a .git subdirectory
-/// * `false` - If the directory does not contain a .git subdirectory
-pub fn is_git_repository(dir: &str) -> bool {
-    std::path::Path::new(dir).join(".git").exists()
+/// * `Some(String)` - The configured URL or path
+/// * `None` - If the directory is not a Git repo or has no origin
+pub fn origin_url(dir: &Path) -> Option<String> {
+    let repo = Repository::open(dir).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    remote.url().map(str::to_string)
+}
+
+/// Fetches all branches and tags from `origin`.
+///
+/// # Arguments
+/// * `dir` - The local clone
+pub fn fetch(dir: &Path) -> Result<()> {
+    let repo = Repository::open(dir)?;
+    let mut remote = repo.find_remote("origin")?;
+    remote
+        .fetch(
+            &[
+                "+refs/heads/*:refs/remotes/origin/*",
+                "+refs/tags/*:refs/tags/*",
+            ],
+            None,
+            None,
+        )
+        .map_err(|e| {
+            format!(
+                "Failed to fetch {}: {}",
+                remote.url().unwrap_or("origin"),
+                e
+            )
+        })?;
+    Ok(())
+}
+
+/// Resolves a branch, tag or commit to a full commit hash.
+///
+/// Branches are looked up on `origin` first, so a fetch moves them forward.
+///
+/// # Arguments
+/// * `dir` - The local clone
+/// * `rev` - A branch name, tag name or (abbreviated) commit hash
+///
+/// # Returns
+/// * `Option<String>` - The 40-character commit hash, if the revision exists
+pub fn resolve_revision(dir: &Path, rev: &str) -> Option<String> {
+    let repo = Repository::open(dir).ok()?;
+    [
+        format!("refs/remotes/origin/{}", rev),
+        format!("refs/tags/{}", rev),
+        rev.to_string(),
+    ]
+    .iter()
+    .find_map(|name| {
+        let object = repo.revparse_single(name).ok()?;
+        let commit = object.peel(ObjectType::Commit).ok()?;
+        Some(commit.id().to_string())
+    })
+}
+
+/// Checks out a commit as a detached HEAD, discarding local changes.
+///
+/// # Arguments
+/// * `dir` - The local clone
+/// * `commit` - The commit hash
+pub fn checkout(dir: &Path, commit: &str) -> Result<()> {
+    let repo = Repository::open(dir)?;
+    repo.set_head_detached(Oid::from_str(commit)?)?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+    Ok(())
+}
+
+/// Formats a Git timestamp in RFC 3339 format with its original offset.
+pub fn format_time(time: git2::Time) -> String {
+    Timestamp::new(time.seconds(), time.offset_minutes() as i64).to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{TempDir, commit};
+    use git2::{Signature, Time};
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(Time::new(0, 0)), "1970-01-01T00:00:00+00:00");
+        assert_eq!(
+            format_time(Time::new(1_709_251_199, -300)),
+            "2024-02-29T18:59:59-05:00"
+        );
+    }
+
+    #[test]
+    fn test_bare_repository_history() {
+        let dir = TempDir::new("git");
+        let repo = Repository::init_bare(&dir).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        let first = commit(
+            &repo,
+            "Ada ada@old.example",
+            1_000_000,
+            &[("README.md", "# A")],
+        );
+        commit(
+            &repo,
+            "dependabot[bot] bot@example.com",
+            2_000_000,
+            &[("deps", "1")],
+        );
+        let signature = Signature::new("Ada", "ada@old.example", &Time::new(2_500_000, 0)).unwrap();
+        repo.tag(
+            "v1",
+            &repo.find_object(first, None).unwrap(),
+            &signature,
+            "First release\n\nNotes",
+            false,
+        )
+        .unwrap();
+        let last = commit(
+            &repo,
+            "Ada ada@new.example",
+            3_000_000,
+            &[
+                ("README.md", "# B"),
+                (
+                    ".mailmap",
+                    "Ada Lovelace <ada@new.example>\nAda Lovelace <ada@new.example> <ada@old.example>\n",
+                ),
+            ],
+        );
+        let dir = dir.to_str().unwrap();
+
+        assert!(is_git_repository(dir));
+        assert_eq!(
+            get_commit_dates(dir, "README.md"),
+            Some((
+                "1970-01-12T15:46:40+02:00".to_string(),
+                "1970-02-04T19:20:00+02:00".to_string()
+            ))
+        );
+        assert_eq!(get_last_commit(dir, "README.md"), Some(last.to_string()));
+        assert_eq!(log(dir, "deps").len(), 1);
+        assert_eq!(get_latest_tag(dir).as_deref(), Some("v1"));
+        assert_eq!(get_default_branch(dir).as_deref(), Some("main"));
+        assert_eq!(read_blob(dir, "v1", "README.md"), Some(b"# A".to_vec()));
+        assert_eq!(
+            get_tags(dir),
+            vec![Tag {
+                name: "v1".to_string(),
+                date: "1970-01-29T22:26:40+00:00".to_string(),
+                message: "First release".to_string(),
+            }]
+        );
+        assert_eq!(
+            get_contributors(dir, None),
+            vec![Contributor {
+                name: "Ada Lovelace".to_string(),
+                email: "ada@new.example".to_string(),
+                commits: 2,
+            }]
+        );
+        assert_eq!(get_contributors(dir, Some("deps")), Vec::new());
+    }
+
+    #[test]
+    fn test_worktree_is_repository() {
+        let root = TempDir::new("worktree");
+        let repo = Repository::init(root.join("main")).unwrap();
+        commit(
+            &repo,
+            "Ada ada@example.com",
+            1_000_000,
+            &[("README.md", "# A")],
+        );
+        repo.worktree("feature", &root.join("feature"), None)
+            .unwrap();
+
+        // A worktree's .git is a file, not a directory
+        assert!(root.join("feature/.git").is_file());
+        assert!(is_git_repository(root.join("feature").to_str().unwrap()));
+        assert!(!is_git_repository(root.to_str().unwrap()));
+    }
 }
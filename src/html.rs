@@ -1,3 +1,7 @@
+use crate::forge::{ForgeKind, ForgeLink, RemoteUrl};
+use crate::git::Contributor;
+use crate::project::Project;
+use crate::releases;
 use regex::Regex;
 
 /// Cleans up code block markup for consistent styling.
@@ -70,26 +74,375 @@ pub fn process_code_blocks(html: &str) -> String {
     wrap_code_blocks(&html)
 }
 
-/// Creates a GitHub repository link section for project pages (bottom placement).
+/// Adds GitHub-style `id` attributes to headings that don't have one.
 ///
-/// This function generates an HTML section with a link to the GitHub repository
-/// if a URL is provided, suitable for placing at the bottom of the page.
+/// Ids are the lowercased heading text with punctuation removed and spaces
+/// replaced by hyphens; repeated ids get a `-1`, `-2`, ... suffix. This keeps
+/// README fragment links such as `#installation` working on the site.
 ///
 /// # Arguments
-/// * `github_url` - Optional GitHub repository URL
+/// * `html` - The HTML content to process
+///
+/// # Returns
+/// * `String` - The HTML with ids on every heading
+pub fn add_heading_ids(html: &str) -> String {
+    let re = Regex::new(r"(?s)<h([1-6])>(.*?)</h([1-6])>").unwrap();
+    let mut seen: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    re.replace_all(html, |caps: &regex::Captures| {
+        let base = slugify(&strip_tags(&caps[2]));
+        let count = seen.entry(base.clone()).or_insert(0);
+        let id = if *count == 0 {
+            base.clone()
+        } else {
+            format!("{}-{}", base, count)
+        };
+        *count += 1;
+        format!("<h{} id=\"{}\">{}</h{}>", &caps[1], id, &caps[2], &caps[3])
+    })
+    .to_string()
+}
+
+/// Converts heading text into a GitHub-style anchor slug.
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Creates the source section for project pages (bottom placement).
+///
+/// The repository link uses the detected forge's icon and name, followed by
+/// the project's optional issues, releases and documentation links and its
+/// release history page. Issues and releases set to `ForgeLink::Derived` link
+/// to the forge's own pages.
+///
+/// # Arguments
+/// * `project` - The project with its repository URL and links
+///
+/// # Returns
+/// * `String` - HTML section with the links, or empty string if there are none
+pub fn create_source_section(project: &Project) -> String {
+    let remote = project.remote();
+    let resolve = |link: &Option<ForgeLink>, forge_url: Option<String>| {
+        link.as_ref().and_then(|link| link.resolve(forge_url))
+    };
+
+    let mut links = Vec::new();
+    if let Some(url) = &project.github_url {
+        let (icon, label) = match &remote {
+            Some(remote) => (
+                remote.kind.icon(),
+                format!(
+                    "{}: {}/{}",
+                    remote.kind.label(),
+                    escape(&remote.owner),
+                    escape(&remote.repo)
+                ),
+            ),
+            None => (ForgeKind::Generic.icon(), escape(url)),
+        };
+        links.push((url.clone(), icon, label));
+    }
+    let issues = resolve(
+        &project.issues_url,
+        remote.as_ref().and_then(RemoteUrl::issues_url),
+    );
+    if let Some(url) = issues {
+        links.push((url, "fas fa-circle-dot", "Issues".to_string()));
+    }
+    let releases = resolve(
+        &project.releases_url,
+        remote.as_ref().and_then(RemoteUrl::releases_url),
+    );
+    if let Some(url) = releases {
+        links.push((url, "fas fa-tag", "Releases".to_string()));
+    }
+    if let Some(url) = &project.docs_url {
+        links.push((url.clone(), "fas fa-book", "Documentation".to_string()));
+    }
+
+    let mut items: Vec<String> = links
+        .iter()
+        .map(|(url, icon, label)| {
+            format!(
+                "<li><a href=\"{}\" target=\"_blank\" rel=\"noopener noreferrer\"><i class=\"{}\" aria-hidden=\"true\"></i> {}</a></li>",
+                escape(url),
+                icon,
+                label
+            )
+        })
+        .collect();
+    let history = format!("{}{}", project.url(), releases::RELEASES_PATH);
+    if project.subpages.contains(&history) {
+        items.push(format!(
+            "<li><a href=\"{}\"><i class=\"fas fa-clock-rotate-left\" aria-hidden=\"true\"></i> Release history</a></li>",
+            history
+        ));
+    }
+
+    if items.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n<h2>Source</h2>\n<ul class=\"source-links\">{}</ul>",
+        items.join("")
+    )
+}
+
+/// Creates the contributors list for project pages.
+///
+/// # Arguments
+/// * `contributors` - The contributors, most commits first
+///
+/// # Returns
+/// * `String` - HTML section with names and commit counts, or empty string if there are none
+pub fn create_contributors_section(contributors: &[Contributor]) -> String {
+    if contributors.is_empty() {
+        return String::new();
+    }
+    let items: Vec<String> = contributors
+        .iter()
+        .map(|contributor| {
+            format!(
+                "<li>{} <span class=\"contributor-commits\">{} {}</span></li>",
+                escape(&contributor.name),
+                contributor.commits,
+                if contributor.commits == 1 {
+                    "commit"
+                } else {
+                    "commits"
+                }
+            )
+        })
+        .collect();
+    format!(
+        "\n<h2>Contributors</h2>\n<ul class=\"contributors\">{}</ul>",
+        items.join("")
+    )
+}
+
+/// Creates the banner marking a page that isn't published yet.
+///
+/// # Arguments
+/// * `project` - The project with its front matter applied
+/// * `today` - The build date, `YYYY-MM-DD`
+///
+/// # Returns
+/// * `String` - The banner HTML, or an empty string for published pages
+pub fn create_draft_banner(project: &Project, today: &str) -> String {
+    let note = match &project.publish_date {
+        Some(date) if project.is_scheduled(today) => format!(
+            "scheduled for <time datetime=\"{0}\">{0}</time>",
+            escape(date)
+        ),
+        _ if project.draft == Some(true) => "not published".to_string(),
+        _ => return String::new(),
+    };
+    format!(
+        "<p class=\"draft-banner\" role=\"note\"><strong>DRAFT</strong> This page is {}.</p>\n",
+        note
+    )
+}
+
+/// Creates the project page footer, e.g. "Last updated 2026-10-01 · v0.4.2 · abc1234".
+///
+/// With a repository URL, the date links to the default branch's history, the
+/// version to its tag and the short hash to the commit.
+///
+/// # Arguments
+/// * `project` - The project with Git metadata filled in
+///
+/// # Returns
+/// * `String` - The `<footer>` HTML, or an empty string if there's nothing to show
+pub fn create_page_footer(project: &Project) -> String {
+    let remote = project.remote();
+    let link = |url: Option<String>, text: String| match url {
+        Some(url) => format!("<a href=\"{}\">{}</a>", escape(&url), text),
+        None => text,
+    };
+
+    let mut parts = Vec::new();
+    if let Some(date) = project.last_updated() {
+        let time = format!("<time datetime=\"{0}\">{0}</time>", escape(date));
+        let history = remote
+            .as_ref()
+            .zip(project.default_branch.as_deref())
+            .and_then(|(remote, branch)| remote.history_url(branch));
+        parts.push(format!("Last updated {}", link(history, time)));
+    }
+    if let Some(version) = &project.version {
+        let tag = remote.as_ref().and_then(|remote| remote.tag_url(version));
+        parts.push(link(tag, escape(version)));
+    }
+    if let Some(commit) = &project.commit {
+        let short = commit.get(..7).unwrap_or(commit);
+        let url = remote.as_ref().and_then(|remote| remote.commit_url(commit));
+        parts.push(link(url, format!("<code>{}</code>", escape(short))));
+    }
+
+    if parts.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n<footer class=\"page-footer\"><p>{}</p></footer>",
+        parts.join(" · ")
+    )
+}
+
+/// Escapes text for safe inclusion in HTML content and attribute values.
+///
+/// # Arguments
+/// * `text` - The raw text
 ///
 /// # Returns
-/// * `String` - HTML section with GitHub link, or empty string if no URL provided
-pub fn create_github_link_section(github_url: &Option<String>) -> String {
-    if let Some(url) = github_url {
-        format!(
-            r#"<h2>Source</h2>
-            <p><a href="{}" target="_blank" rel="noopener noreferrer"><i class="fab fa-github"></i> {}</a></p>"#,
-            url, url
-        )
-    } else {
-        String::new()
+/// * `String` - The text with `&`, `<`, `>`, `"` and `'` escaped
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
     }
+    escaped
+}
+
+/// Removes HTML tags, decodes basic entities and collapses whitespace, leaving plain text.
+///
+/// The result is raw text and must be passed through [`escape`] before being embedded in HTML.
+///
+/// # Arguments
+/// * `html` - The HTML fragment
+///
+/// # Returns
+/// * `String` - The text content of the fragment
+pub fn strip_tags(html: &str) -> String {
+    let re = Regex::new(r"<[^>]*>").unwrap();
+    let text = re
+        .replace_all(html, "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Extracts the text of the first `<h1>` element.
+///
+/// # Arguments
+/// * `html` - The HTML content to scan
+///
+/// # Returns
+/// * `Option<String>` - The heading text, if there is a non-empty `<h1>`
+pub fn extract_first_heading(html: &str) -> Option<String> {
+    let re = Regex::new(r"(?s)<h1[^>]*>(.*?)</h1>").unwrap();
+    let text = strip_tags(&re.captures(html)?[1]);
+    (!text.is_empty()).then_some(text)
+}
+
+/// Extracts the text of the first paragraph that contains any text.
+///
+/// Paragraphs holding only images or badges are skipped.
+///
+/// # Arguments
+/// * `html` - The HTML content to scan
+///
+/// # Returns
+/// * `Option<String>` - The paragraph text, if any
+pub fn extract_first_paragraph(html: &str) -> Option<String> {
+    let re = Regex::new(r"(?s)<p[^>]*>(.*?)</p>").unwrap();
+    re.captures_iter(html)
+        .map(|caps| strip_tags(&caps[1]))
+        .find(|text| !text.is_empty())
+}
+
+/// Creates the project listing grid for the home page.
+///
+/// Each project becomes a card with its logo, title, description, status,
+/// last-updated date and source link. Cards are rendered in the order given.
+///
+/// # Arguments
+/// * `projects` - The processed projects, already sorted for display
+///
+/// # Returns
+/// * `String` - HTML for the project grid, or empty string if there are no projects
+pub fn create_project_grid(projects: &[&Project]) -> String {
+    if projects.is_empty() {
+        return String::new();
+    }
+
+    let cards: Vec<String> = projects
+        .iter()
+        .map(|project| {
+            let url = project.url();
+            let mut card = String::new();
+            if let Some(logo) = &project.logo {
+                card.push_str(&format!(
+                    "<img class=\"project-logo\" src=\"{}{}\" alt=\"{} logo\" />",
+                    url,
+                    escape(logo),
+                    escape(project.display_title())
+                ));
+            }
+            card.push_str(&format!(
+                "<h3><a href=\"{}\">{}</a></h3>",
+                url,
+                escape(project.display_title())
+            ));
+            if let Some(description) = &project.description {
+                card.push_str(&format!("<p>{}</p>", escape(description)));
+            }
+
+            let mut details = Vec::new();
+            if let Some(status) = &project.status {
+                details.push(format!(
+                    "<span class=\"project-status\">{}</span>",
+                    escape(status)
+                ));
+            }
+            if let Some(date) = project.last_updated() {
+                details.push(format!(
+                    "<span>Updated <time datetime=\"{0}\">{0}</time></span>",
+                    escape(date)
+                ));
+            }
+            if let Some(github_url) = &project.github_url {
+                let kind = project.remote().map_or(ForgeKind::Generic, |remote| remote.kind);
+                details.push(format!(
+                    "<a href=\"{}\" target=\"_blank\" rel=\"noopener noreferrer\"><i class=\"{}\" aria-hidden=\"true\"></i> Source</a>",
+                    escape(github_url),
+                    kind.icon()
+                ));
+            }
+            if !details.is_empty() {
+                card.push_str(&format!(
+                    "<p class=\"project-details\">{}</p>",
+                    details.join(" · ")
+                ));
+            }
+
+            let class = if project.is_featured() {
+                "project-card featured"
+            } else {
+                "project-card"
+            };
+            format!("<li class=\"{}\">{}</li>", class, card)
+        })
+        .collect();
+
+    format!("<ul class=\"project-grid\">\n{}\n</ul>", cards.join("\n"))
 }
 
 #[cfg(test)]
@@ -120,14 +473,148 @@ mod tests {
     }
 
     #[test]
-    fn test_create_github_link_section() {
-        let url = Some("https://github.com/user/repo".to_string());
-        let result = create_github_link_section(&url);
-        assert!(result.contains("<i class=\"fab fa-github\"></i> GitHub Repository:"));
-        assert!(result.contains("https://github.com/user/repo"));
-
-        let no_url: Option<String> = None;
-        let empty_result = create_github_link_section(&no_url);
-        assert_eq!(empty_result, "");
+    fn test_create_contributors_section() {
+        assert_eq!(create_contributors_section(&[]), "");
+        let contributors = [
+            Contributor {
+                name: "Ada <Lovelace>".to_string(),
+                email: "ada@example.com".to_string(),
+                commits: 42,
+            },
+            Contributor {
+                name: "Grace".to_string(),
+                email: "grace@example.com".to_string(),
+                commits: 1,
+            },
+        ];
+        let html = create_contributors_section(&contributors);
+        assert!(html.contains(
+            "<li>Ada &lt;Lovelace&gt; <span class=\"contributor-commits\">42 commits</span></li>"
+        ));
+        assert!(
+            html.contains("<li>Grace <span class=\"contributor-commits\">1 commit</span></li>")
+        );
+        assert!(!html.contains("example.com"));
+    }
+
+    #[test]
+    fn test_create_page_footer() {
+        let mut project = Project::new("../x".to_string(), "docs/projects/x".to_string());
+        assert_eq!(create_page_footer(&project), "");
+
+        project.updated = Some("2026-10-01T09:30:00+02:00".to_string());
+        project.version = Some("v0.4.2".to_string());
+        project.commit = Some("abc1234def5678".to_string());
+        assert_eq!(
+            create_page_footer(&project),
+            "\n<footer class=\"page-footer\"><p>Last updated <time datetime=\"2026-10-01\">2026-10-01</time> · v0.4.2 · <code>abc1234</code></p></footer>"
+        );
+
+        project.github_url = Some("https://gitlab.com/user/x".to_string());
+        project.default_branch = Some("main".to_string());
+        let footer = create_page_footer(&project);
+        assert!(footer.contains("<a href=\"https://gitlab.com/user/x/-/commits/main\"><time"));
+        assert!(footer.contains("<a href=\"https://gitlab.com/user/x/-/tags/v0.4.2\">v0.4.2</a>"));
+        assert!(footer.contains(
+            "<a href=\"https://gitlab.com/user/x/-/commit/abc1234def5678\"><code>abc1234</code></a>"
+        ));
+    }
+
+    #[test]
+    fn test_create_draft_banner() {
+        let mut project = Project::new("../x".to_string(), "docs/projects/x".to_string());
+        assert_eq!(create_draft_banner(&project, "2026-10-19"), "");
+
+        project.publish_date = Some("2026-11-01".to_string());
+        assert!(create_draft_banner(&project, "2026-10-19").contains(
+            "<strong>DRAFT</strong> This page is scheduled for <time datetime=\"2026-11-01\">"
+        ));
+        assert_eq!(create_draft_banner(&project, "2026-11-01"), "");
+
+        project.draft = Some(true);
+        assert!(
+            create_draft_banner(&project, "2026-11-01").contains("This page is not published.")
+        );
+    }
+
+    #[test]
+    fn test_create_source_section() {
+        let mut project = Project::new("../repo".to_string(), "docs/projects/repo".to_string());
+        assert_eq!(create_source_section(&project), "");
+
+        project.github_url = Some("https://github.com/user/repo".to_string());
+        let result = create_source_section(&project);
+        assert!(result.contains(
+            "<a href=\"https://github.com/user/repo\" target=\"_blank\" rel=\"noopener noreferrer\"><i class=\"fab fa-github\" aria-hidden=\"true\"></i> GitHub: user/repo</a>"
+        ));
+        assert!(!result.contains("Issues"));
+
+        project.github_url = Some("https://codeberg.org/user/repo".to_string());
+        project.issues_url = Some(ForgeLink::Derived);
+        project.releases_url = Some(ForgeLink::Url("https://example.com/changes".to_string()));
+        project.docs_url = Some("https://docs.example.com".to_string());
+        let result = create_source_section(&project);
+        assert!(
+            result.contains(
+                "<i class=\"fab fa-git-alt\" aria-hidden=\"true\"></i> Codeberg: user/repo"
+            )
+        );
+        assert!(result.contains("href=\"https://codeberg.org/user/repo/issues\""));
+        assert!(result.contains("href=\"https://example.com/changes\""));
+        assert!(result.contains("href=\"https://docs.example.com\""));
+    }
+
+    #[test]
+    fn test_add_heading_ids() {
+        let html = "<h1>Reservoir</h1><h2>Install &amp; Run!</h2><h2>Usage</h2><h2>Usage</h2><h3 id=\"keep\">Kept</h3>";
+        let result = add_heading_ids(html);
+        assert_eq!(
+            result,
+            "<h1 id=\"reservoir\">Reservoir</h1><h2 id=\"install--run\">Install &amp; Run!</h2><h2 id=\"usage\">Usage</h2><h2 id=\"usage-1\">Usage</h2><h3 id=\"keep\">Kept</h3>"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_extract_first_heading_and_paragraph() {
+        let html = "<h1>Reservoir <code>v1</code></h1>\n<p><img src=\"logo.png\"></p>\n<p>A <em>memory</em> &amp; store.</p>";
+        assert_eq!(
+            extract_first_heading(html),
+            Some("Reservoir v1".to_string())
+        );
+        assert_eq!(
+            extract_first_paragraph(html),
+            Some("A memory & store.".to_string())
+        );
+        assert_eq!(extract_first_heading("<p>none</p>"), None);
+    }
+
+    #[test]
+    fn test_create_project_grid() {
+        let featured = Project::new("../a".to_string(), "docs/projects/a".to_string())
+            .with_title("Alpha & Co")
+            .with_status("active")
+            .with_featured(true);
+        let mut plain = Project::new("../b".to_string(), "docs/projects/b".to_string());
+        plain.logo = Some("logo.png".to_string());
+        plain.updated = Some("2026-10-01T09:30:00+02:00".to_string());
+
+        let result = create_project_grid(&[&featured, &plain]);
+        assert!(result.contains(r#"<li class="project-card featured"><h3><a href="/projects/a/">Alpha &amp; Co</a></h3>"#));
+        assert!(result.contains(r#"<span class="project-status">active</span>"#));
+        assert!(
+            result.contains(
+                r#"<img class="project-logo" src="/projects/b/logo.png" alt="b logo" />"#
+            )
+        );
+        assert!(result.contains(r#"<time datetime="2026-10-01">2026-10-01</time>"#));
+        assert_eq!(create_project_grid(&[]), "");
     }
 }
@@ -132,11 +132,35 @@ pub fn fix_image_paths(html: &str, image_paths: &[String]) -> String {
 /// * `output_dir` - The destination directory where images should be copied
 ///
 /// # Returns
-/// * `Result<String>` - The processed HTML with updated image paths on success, or an error
-pub fn process_images(html: &str, source_dir: &str, output_dir: &str) -> Result<String> {
+/// * `Result<(String, Vec<String>)>` - The processed HTML with updated image paths and the
+///   copied image filenames on success, or an error
+pub fn process_images(
+    html: &str,
+    source_dir: &str,
+    output_dir: &str,
+) -> Result<(String, Vec<String>)> {
     let image_paths = extract_image_paths(html);
     let copied_images = copy_images_flat(source_dir, output_dir, &image_paths)?;
-    Ok(fix_image_paths(html, &copied_images))
+    Ok((fix_image_paths(html, &copied_images), copied_images))
+}
+
+/// Picks the image most likely to be a project's logo.
+///
+/// Returns the first filename containing "logo", falling back to the first
+/// containing "icon".
+///
+/// # Arguments
+/// * `filenames` - Flattened image filenames copied for the project
+///
+/// # Returns
+/// * `Option<String>` - The logo filename, if one looks like a logo
+pub fn find_logo(filenames: &[String]) -> Option<String> {
+    ["logo", "icon"].iter().find_map(|needle| {
+        filenames
+            .iter()
+            .find(|name| name.to_lowercase().contains(needle))
+            .cloned()
+    })
 }
 
 #[cfg(test)]
@@ -173,6 +197,18 @@ mod tests {
         assert!(paths.is_empty());
     }
 
+    #[test]
+    fn test_find_logo() {
+        let names = vec![
+            "screenshot.png".to_string(),
+            "icon_256x256.png".to_string(),
+            "logo_256.png".to_string(),
+        ];
+        assert_eq!(find_logo(&names), Some("logo_256.png".to_string()));
+        assert_eq!(find_logo(&names[..2]), Some("icon_256x256.png".to_string()));
+        assert_eq!(find_logo(&names[..1]), None);
+    }
+
     #[test]
     fn test_fix_image_paths_multiple() {
         let html = r#"
@@ -1,49 +1,386 @@
+mod cache;
+mod check;
+mod cli;
+mod date;
+mod feed;
+mod forge;
+mod frontmatter;
 mod git;
 mod html;
 mod images;
+mod navigation;
+mod not_found;
+mod package;
+mod pool;
 mod project;
+mod redirects;
+mod releases;
+mod search;
+mod serve;
+mod site;
+mod sitemap;
+mod social;
+mod sources;
+mod structured_data;
+mod tags;
 mod templates;
+#[cfg(test)]
+mod test_support;
+mod url;
+mod validate;
 
+use cache::BuildCache;
+use cli::Command;
+use feed::FeedEntry;
+use forge::ForgeLink;
 use project::{Project, SiteLink};
+use redirects::Redirect;
+use search::SearchDocument;
+use site::Site;
+use sitemap::SitemapEntry;
+use social::CardRenderer;
+use sources::{LockMode, Lockfile};
 use std::error::Error;
-use std::fs;
+use std::path::Path;
+use std::{env, fs};
+use templates::PageMeta;
+use validate::{Rule, Severity};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
+/// The published site, served by GitHub Pages.
+const OUTPUT_DIR: &str = "docs";
+
+/// Where builds with drafts go, so previews never end up in the published site.
+const PREVIEW_DIR: &str = ".site-cache/preview";
+
 fn main() -> Result<()> {
+    let options = cli::parse(env::args().skip(1))?;
+
+    // Define site-wide settings
+    let mut site = Site::new(
+        "Sector F Labs",
+        "https://sectorflabs.com",
+        "Sector F Labs is a space for bold experimentation and beautifully simple tools. We break traditional molds to build systems that are powerful, privacy-respecting, and human-centered.",
+    );
+    site.feed_rss = true;
+    site.drafts = options.drafts || options.command == Command::Serve;
+
+    // Site navigation; an empty URL makes a heading for the links under it
+    site.menu = vec![
+        SiteLink::new(
+            "CMF".to_string(),
+            "/conversational-markdown-format/".to_string(),
+        ),
+        SiteLink::new("Projects".to_string(), "/#projects".to_string()).with_children(vec![
+            SiteLink::new("Reservoir".to_string(), "/projects/reservoir/".to_string()),
+            SiteLink::new("MD-Chat".to_string(), "/projects/md-chat/".to_string()),
+        ]),
+        SiteLink::new("Experiments".to_string(), String::new()).with_children(vec![SiteLink::new(
+            "Service Pipe".to_string(),
+            "/projects/exp-013-service-pipe/".to_string(),
+        )]),
+        SiteLink::new(
+            "GitHub".to_string(),
+            "https://github.com/Sector-F-Labs".to_string(),
+        ),
+    ];
+
+    // Old URLs kept working after pages move
+    site.redirects
+        .push(Redirect::new("/reservoir/", "/projects/reservoir/"));
+
+    // Project READMEs are written for GitHub first; report their accessibility gaps without failing
+    for rule in [
+        Rule::MissingAlt,
+        Rule::SkippedHeading,
+        Rule::UnlabeledButton,
+    ] {
+        site.validation.set(rule, Severity::Warning);
+    }
+
+    let lock_mode = if options.update {
+        LockMode::Update
+    } else if options.locked {
+        LockMode::Locked
+    } else {
+        LockMode::Resolve
+    };
+
+    match options.command {
+        Command::Build => build(&site, options.force, lock_mode, options.jobs),
+        Command::Check => check(&site, options.external),
+        Command::Serve => {
+            build(&site, options.force, lock_mode, options.jobs)?;
+            serve::serve(PREVIEW_DIR, serve::ADDRESS)
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
+
+/// Generates the whole site into `docs/` using `jobs` worker threads, reusing unchanged
+/// projects unless `force` is set and treating sources.lock as `lock_mode` says.
+/// Builds with drafts go to `PREVIEW_DIR` instead, on top of a copy of `docs/` for
+/// the stylesheet, fonts and images.
+fn build(site: &Site, force: bool, lock_mode: LockMode, jobs: usize) -> Result<()> {
+    let output = if site.drafts { PREVIEW_DIR } else { OUTPUT_DIR };
+    if site.drafts {
+        if fs::metadata(PREVIEW_DIR).is_ok_and(|meta| meta.is_dir()) {
+            fs::remove_dir_all(PREVIEW_DIR)?;
+        }
+        copy_dir(Path::new(OUTPUT_DIR), Path::new(PREVIEW_DIR))?;
+        println!("👀 Building a preview with drafts into {}/", PREVIEW_DIR);
+    }
+
     // Define projects to process
     let mut projects = vec![
-        Project::new(
-            "../reservoir".to_string(),
+        Project::from_git(
+            "https://github.com/Sector-F-Labs/reservoir".to_string(),
+            "main".to_string(),
             "docs/projects/reservoir".to_string(),
-        ),
+        )
+        .with_title("Reservoir")
+        .with_status("active")
+        .with_featured(true)
+        .with_order(1)
+        .with_tags(&["llm", "rust"])
+        .with_issues_url(ForgeLink::Derived)
+        .with_releases_url(ForgeLink::Derived)
+        .with_release_history(true),
     ];
 
-    // Define site navigation links
-    let links = vec![
-        SiteLink::new("CMF".to_string(), "/conversational-markdown-format/".to_string()),
-        SiteLink::new("Reservoir".to_string(), "/projects/reservoir".to_string()),
-    ];
+    for project in &mut projects {
+        project.rebase_output(OUTPUT_DIR, output);
+    }
+
+    // Check out git sources at the commits pinned in sources.lock, or the latest on --update
+    let mut lockfile = Lockfile::load(sources::LOCKFILE)?;
+    sources::fetch_sources(&projects, &mut lockfile, jobs, lock_mode)?;
+    lockfile.save(sources::LOCKFILE)?;
+
+    // Read Git metadata and front matter
+    project::prepare_projects(&mut projects, jobs);
+
+    // Hold back drafts and scheduled pages, removing output left by earlier draft builds
+    let mut held_back = Vec::new();
+    if !site.drafts {
+        for project in projects.iter().filter(|p| p.is_draft(&site.build_date)) {
+            if fs::metadata(&project.output_dir).is_ok_and(|meta| meta.is_dir()) {
+                fs::remove_dir_all(&project.output_dir)?;
+            }
+            println!("📝 Held back draft {}", project.name());
+            held_back.push(project.url());
+        }
+        projects.retain(|project| !project.is_draft(&site.build_date));
+    }
 
-    // Generate navigation menu HTML
-    let menu_links: Vec<(&str, &str)> = links
+    // Link each project to its breadcrumbs and sibling pages
+    navigation::link_projects(&mut projects);
+
+    // Leave held-back drafts out of the navigation
+    let site = &Site {
+        menu: site
+            .menu
+            .iter()
+            .filter_map(|link| {
+                link.filter(&|url| {
+                    !held_back
+                        .iter()
+                        .any(|held| held.trim_end_matches('/') == url.trim_end_matches('/'))
+                })
+            })
+            .collect(),
+        ..site.clone()
+    };
+
+    // Load and process layout template; the menu is filled in per page
+    let layout_template = include_str!("./templates/layout.html");
+    let feed_links = feed::create_feed_links(site, site.feed_rss);
+    let layout = templates::replace_template(layout_template, &[("{{ feed_links }}", &feed_links)]);
+
+    // Load fonts, logo and brand colours for social cards
+    let cards = CardRenderer::load(OUTPUT_DIR)?;
+
+    // Process all projects; the site settings and layout shape every page and the
+    // stylesheet and card logo feed into every social card
+    let mut cache = BuildCache::new(
+        cache::CACHE_DIR,
+        force,
+        site,
+        &layout,
+        &["docs/style.css", "docs/logo-transparent.png"],
+    );
+    project::process_projects(&mut projects, site, &layout, &cards, &mut cache, jobs)?;
+    cache.print_summary();
+
+    // Generate index page with the project listing
+    let project_grid = html::create_project_grid(&project::sort_for_listing(&projects));
+    let index_content = html::add_heading_ids(&templates::replace_template(
+        include_str!("./pages/index.html"),
+        &[("{{ project_list }}", &project_grid)],
+    ));
+    cards.write(output, &site.name, &site.name, Some(&site.description))?;
+    let index_meta = PageMeta {
+        title: site.page_title(None),
+        description: site.description.clone(),
+        canonical_url: site.absolute_url("/"),
+        image_url: site.absolute_url(&format!("/{}", social::CARD_FILENAME)),
+        structured_data: structured_data::create_site_json_ld(site),
+    };
+    let index_layout = templates::apply_page_meta(&layout, &index_meta);
+    let index_html =
+        templates::process_template(&index_layout, &index_content, &site.menu_html("/"));
+    fs::write(format!("{}/index.html", output), index_html)?;
+
+    // Generate the 404 page GitHub Pages serves for missing URLs
+    not_found::write_not_found_page(output, site, &layout, &projects)?;
+
+    // Generate tag listing pages
+    let tag_pages = tags::write_tag_pages(output, site, &layout, &projects)?;
+
+    // Generate sitemap.xml and robots.txt
+    let mut sitemap_entries = vec![SitemapEntry {
+        url: site.absolute_url("/"),
+        lastmod: git::get_commit_dates(".", "src/pages/index.html").map(|(_, updated)| updated),
+    }];
+    sitemap_entries.extend(
+        projects
+            .iter()
+            .filter(|project| project.in_sitemap())
+            .flat_map(|project| {
+                std::iter::once(project.url())
+                    .chain(project.subpages.iter().cloned())
+                    .map(|url| SitemapEntry {
+                        url: site.absolute_url(&url),
+                        lastmod: project.updated.clone(),
+                    })
+            }),
+    );
+    sitemap_entries.extend(tag_pages.iter().map(|page| SitemapEntry {
+        url: site.absolute_url(&page.url),
+        lastmod: None,
+    }));
+    fs::write(
+        format!("{}/sitemap.xml", output),
+        sitemap::create_sitemap(&sitemap_entries),
+    )?;
+    fs::write(
+        format!("{}/robots.txt", output),
+        sitemap::create_robots_txt(&site.absolute_url("/sitemap.xml")),
+    )?;
+
+    // Generate Atom and RSS feeds of project updates
+    let feed_entries: Vec<FeedEntry> = projects
         .iter()
-        .map(|link| (link.name.as_str(), link.url.as_str()))
+        .filter_map(|project| FeedEntry::from_project(project, site, site.feed_full_content))
         .collect();
-    let menu_html = templates::create_menu_html(&menu_links);
+    fs::write(
+        format!("{}/atom.xml", output),
+        feed::create_atom_feed(site, &site.absolute_url("/atom.xml"), &feed_entries),
+    )?;
+    if site.feed_rss {
+        fs::write(
+            format!("{}/rss.xml", output),
+            feed::create_rss_feed(site, &site.absolute_url("/rss.xml"), &feed_entries),
+        )?;
+    }
 
-    // Load and process layout template
-    let layout_template = include_str!("./templates/layout.html");
-    let layout = templates::replace_template(layout_template, &[("{{ menu_items }}", &menu_html)]);
+    // Generate the client-side search index
+    let mut search_documents = vec![SearchDocument::from_html(
+        &site.name,
+        "/",
+        &[],
+        &index_content,
+    )];
+    search_documents.extend(projects.iter().filter_map(|project| {
+        let content = project.content.as_ref()?;
+        Some(SearchDocument::from_html(
+            project.display_title(),
+            &project.url(),
+            &project.tags,
+            content,
+        ))
+    }));
+    search_documents.extend(
+        tag_pages
+            .iter()
+            .filter(|page| !page.tag.is_empty())
+            .map(|page| {
+                SearchDocument::from_html(
+                    &page.title,
+                    &page.url,
+                    std::slice::from_ref(&page.tag),
+                    &page.content,
+                )
+            }),
+    );
+    let search_index = search::create_search_index(&search_documents);
+    fs::write(format!("{}/search-index.json", output), &search_index)?;
+    println!(
+        "🔎 Search index v{}: {} pages, {:.1} KB",
+        search::INDEX_VERSION,
+        search_documents.len(),
+        search_index.len() as f64 / 1024.0
+    );
 
-    // Process all projects
-    project::process_projects(&mut projects, &layout)?;
+    // Write redirect stubs for moved pages and project aliases, skipping held-back drafts
+    let mut redirects: Vec<Redirect> = site
+        .redirects
+        .iter()
+        .filter(|redirect| !held_back.contains(&redirect.to))
+        .cloned()
+        .collect();
+    redirects.extend(projects.iter().flat_map(|project| {
+        project
+            .aliases
+            .iter()
+            .map(|alias| Redirect::new(alias, &project.url()))
+    }));
+    let redirect_count = redirects::write_redirects(output, site, &redirects)?;
+    println!("↪️  Wrote {} redirects", redirect_count);
 
-    // Generate index page
-    let index_content = include_str!("./pages/index.html");
-    let index_html = templates::process_template(&layout, index_content, &menu_html);
-    fs::write("docs/index.html", index_html)?;
+    // Validate the generated HTML
+    let validation = validate::validate_site(output, &site.validation, jobs)?;
+    validation.print();
+    if validation.error_count() > 0 {
+        return Err(format!(
+            "HTML validation failed with {} errors",
+            validation.error_count()
+        )
+        .into());
+    }
 
     println!("🎉 Site generation completed successfully!");
     Ok(())
 }
+
+/// Copies a directory tree.
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks links in the generated site and fails if any are broken.
+fn check(site: &Site, external: bool) -> Result<()> {
+    let external_links =
+        external.then(|| check::ExternalLinks::load(check::ALLOWLIST_FILE, check::CACHE_FILE));
+    let report = check::check_site(OUTPUT_DIR, &site.base_url, external_links.as_ref())?;
+    report.print();
+
+    match report.error_count() {
+        0 => Ok(()),
+        count => Err(format!("Link check failed with {} errors", count).into()),
+    }
+}
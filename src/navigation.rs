@@ -0,0 +1,259 @@
+//! Breadcrumbs and previous/next links between sibling pages.
+//!
+//! Breadcrumbs follow the output path, so `/projects/reservoir/releases/`
+//! reads Home › Projects › Reservoir › Release history. Path segments without
+//! a page of their own, like `/projects/`, are shown as plain text. Sibling
+//! pages share a parent path and are ordered by URL, which keeps numbered
+//! pages such as `exp-012` and `exp-014` next to each other.
+
+use crate::html;
+use crate::project::{Project, SiteLink};
+use crate::search::json_string;
+use crate::site::Site;
+use std::collections::BTreeMap;
+
+/// Builds the breadcrumb trail for a page, ending with the page itself.
+///
+/// # Arguments
+/// * `url` - The site-relative URL of the page, e.g. `/projects/reservoir/`
+/// * `titles` - Titles of the site's pages by URL; pages missing here are not linked
+///
+/// # Returns
+/// * `Vec<SiteLink>` - Home first, then each ancestor; ancestors without a page have an empty URL
+pub fn breadcrumbs(url: &str, titles: &BTreeMap<String, String>) -> Vec<SiteLink> {
+    let mut trail = vec![SiteLink::new("Home".to_string(), "/".to_string())];
+    let mut path = String::from("/");
+    for segment in url.split('/').filter(|segment| !segment.is_empty()) {
+        path.push_str(segment);
+        path.push('/');
+        trail.push(match titles.get(&path) {
+            Some(title) => SiteLink::new(title.clone(), path.clone()),
+            None if path == url => SiteLink::new(segment_title(segment), path.clone()),
+            None => SiteLink::new(segment_title(segment), String::new()),
+        });
+    }
+    trail
+}
+
+/// Turns a path segment such as `md-chat` into a label such as `Md chat`.
+fn segment_title(segment: &str) -> String {
+    let words = segment.replace(['-', '_'], " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Sets each project's breadcrumbs and its previous and next siblings.
+///
+/// Call this after drafts are held back so they are never linked.
+///
+/// # Arguments
+/// * `projects` - The projects being built
+pub fn link_projects(projects: &mut [Project]) {
+    let titles: BTreeMap<String, String> = projects
+        .iter()
+        .map(|project| (project.url(), project.display_title().to_string()))
+        .collect();
+
+    let mut siblings: BTreeMap<String, Vec<(String, usize)>> = BTreeMap::new();
+    for (index, project) in projects.iter().enumerate() {
+        let url = project.url();
+        let parent = parent_url(&url);
+        siblings.entry(parent).or_default().push((url, index));
+    }
+
+    for group in siblings.values_mut() {
+        group.sort();
+        for (position, (_, index)) in group.iter().enumerate() {
+            let link = |position: Option<usize>| {
+                let (url, index) = group.get(position?)?;
+                Some(SiteLink::new(
+                    projects[*index].display_title().to_string(),
+                    url.clone(),
+                ))
+            };
+            let previous = link(position.checked_sub(1));
+            let next = link(Some(position + 1));
+            let project = &mut projects[*index];
+            project.breadcrumbs = breadcrumbs(&project.url(), &titles);
+            project.previous = previous;
+            project.next = next;
+        }
+    }
+}
+
+/// Gets the URL one level up, e.g. `/projects/` for `/projects/reservoir/`.
+fn parent_url(url: &str) -> String {
+    let trimmed = url.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(end) => trimmed[..=end].to_string(),
+        None => "/".to_string(),
+    }
+}
+
+/// Creates the breadcrumb trail HTML, marking the last entry as the current page.
+///
+/// # Arguments
+/// * `trail` - The breadcrumbs from `breadcrumbs`
+///
+/// # Returns
+/// * `String` - The breadcrumb `<nav>`, or an empty string for the home page
+pub fn create_breadcrumbs_html(trail: &[SiteLink]) -> String {
+    if trail.len() < 2 {
+        return String::new();
+    }
+    let items: Vec<String> = trail
+        .iter()
+        .enumerate()
+        .map(|(index, crumb)| {
+            let name = html::escape(&crumb.name);
+            if index + 1 == trail.len() {
+                format!(
+                    "<li><a href=\"{}\" aria-current=\"page\">{}</a></li>",
+                    html::escape(&crumb.url),
+                    name
+                )
+            } else if crumb.url.is_empty() {
+                format!("<li>{}</li>", name)
+            } else {
+                format!(
+                    "<li><a href=\"{}\">{}</a></li>",
+                    html::escape(&crumb.url),
+                    name
+                )
+            }
+        })
+        .collect();
+    format!(
+        "<nav class=\"breadcrumbs\" aria-label=\"Breadcrumb\"><ol>{}</ol></nav>\n",
+        items.join("")
+    )
+}
+
+/// Creates `BreadcrumbList` structured data for the trail.
+///
+/// Entries without a page are left out, since every item but the last needs a URL.
+///
+/// # Arguments
+/// * `site` - The site settings, used for absolute URLs
+/// * `trail` - The breadcrumbs from `breadcrumbs`
+///
+/// # Returns
+/// * `String` - A JSON-LD `<script>` element, or an empty string for the home page
+pub fn create_breadcrumbs_json_ld(site: &Site, trail: &[SiteLink]) -> String {
+    if trail.len() < 2 {
+        return String::new();
+    }
+    let items: Vec<String> = trail
+        .iter()
+        .filter(|crumb| !crumb.url.is_empty())
+        .enumerate()
+        .map(|(index, crumb)| {
+            format!(
+                "{{\"@type\":\"ListItem\",\"position\":{},\"name\":{},\"item\":{}}}",
+                index + 1,
+                json_string(&crumb.name),
+                json_string(&site.absolute_url(&crumb.url))
+            )
+        })
+        .collect();
+    format!(
+        "<script type=\"application/ld+json\">{{\"@context\":\"https://schema.org\",\"@type\":\"BreadcrumbList\",\"itemListElement\":[{}]}}</script>\n",
+        items.join(",")
+    )
+}
+
+/// Creates the links to the previous and next sibling pages.
+///
+/// # Arguments
+/// * `previous` - The sibling before this page, if any
+/// * `next` - The sibling after this page, if any
+///
+/// # Returns
+/// * `String` - The sibling `<nav>`, or an empty string if the page has no siblings
+pub fn create_sibling_links(previous: Option<&SiteLink>, next: Option<&SiteLink>) -> String {
+    if previous.is_none() && next.is_none() {
+        return String::new();
+    }
+    let link = |link: Option<&SiteLink>, rel: &str, label: &str| match link {
+        Some(link) => format!(
+            "<a href=\"{}\" rel=\"{}\"><span>{}</span> {}</a>",
+            html::escape(&link.url),
+            rel,
+            label,
+            html::escape(&link.name)
+        ),
+        None => String::new(),
+    };
+    format!(
+        "\n<nav class=\"sibling-links\" aria-label=\"Previous and next pages\">{}{}</nav>\n",
+        link(previous, "prev", "Previous:"),
+        link(next, "next", "Next:")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, title: &str) -> Project {
+        let mut project = Project::new(format!("../{}", name), format!("docs/projects/{}", name));
+        project.title = Some(title.to_string());
+        project
+    }
+
+    #[test]
+    fn test_breadcrumbs() {
+        let titles =
+            BTreeMap::from([("/projects/reservoir/".to_string(), "Reservoir".to_string())]);
+        let trail = breadcrumbs("/projects/reservoir/releases/", &titles);
+        let names: Vec<(&str, &str)> = trail
+            .iter()
+            .map(|crumb| (crumb.name.as_str(), crumb.url.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Home", "/"),
+                ("Projects", ""),
+                ("Reservoir", "/projects/reservoir/"),
+                ("Releases", "/projects/reservoir/releases/"),
+            ]
+        );
+        assert_eq!(
+            create_breadcrumbs_html(&trail),
+            "<nav class=\"breadcrumbs\" aria-label=\"Breadcrumb\"><ol><li><a href=\"/\">Home</a></li><li>Projects</li><li><a href=\"/projects/reservoir/\">Reservoir</a></li><li><a href=\"/projects/reservoir/releases/\" aria-current=\"page\">Releases</a></li></ol></nav>\n"
+        );
+        assert_eq!(create_breadcrumbs_html(&breadcrumbs("/", &titles)), "");
+
+        let site = Site::new("Sector F Labs", "https://sectorflabs.com", "");
+        let json_ld = create_breadcrumbs_json_ld(&site, &trail);
+        assert!(json_ld.contains("{\"@type\":\"ListItem\",\"position\":2,\"name\":\"Reservoir\",\"item\":\"https://sectorflabs.com/projects/reservoir/\"}"));
+        assert!(!json_ld.contains("\"Projects\""));
+    }
+
+    #[test]
+    fn test_link_projects() {
+        let mut projects = vec![
+            project("exp-014", "Exp 14"),
+            project("exp-012", "Exp 12"),
+            project("exp-013", "Exp 13"),
+        ];
+        link_projects(&mut projects);
+
+        let middle = &projects[2];
+        assert_eq!(middle.previous.as_ref().unwrap().url, "/projects/exp-012/");
+        assert_eq!(middle.next.as_ref().unwrap().url, "/projects/exp-014/");
+        assert!(projects[1].previous.is_none());
+        assert!(projects[0].next.is_none());
+        assert_eq!(middle.breadcrumbs.last().unwrap().name, "Exp 13");
+
+        assert_eq!(
+            create_sibling_links(None, projects[1].next.as_ref()),
+            "\n<nav class=\"sibling-links\" aria-label=\"Previous and next pages\"><a href=\"/projects/exp-013/\" rel=\"next\"><span>Next:</span> Exp 13</a></nav>\n"
+        );
+        assert_eq!(create_sibling_links(None, None), "");
+    }
+}
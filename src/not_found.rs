@@ -0,0 +1,144 @@
+//! The `404.html` page served for missing URLs.
+//!
+//! GitHub Pages serves `404.html` from the site root for any path that doesn't
+//! exist, so every link on it must be site-absolute. The page goes through the
+//! normal layout, keeping the header and menu, and can add a search box and
+//! the project listing to help visitors find their way.
+
+use crate::frontmatter;
+use crate::html;
+use crate::project::{self, Project};
+use crate::site::Site;
+use crate::social;
+use crate::structured_data;
+use crate::templates::{self, PageMeta};
+use std::error::Error;
+use std::fs;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Built-in page body used when no source file is configured.
+const DEFAULT_CONTENT: &str = include_str!("./pages/404.html");
+
+/// Search box rendered in the page body; `search.js` binds every `form.search`.
+const SEARCH_BOX: &str = "<form class=\"search\" role=\"search\" onsubmit=\"return false\">
+<input type=\"search\" placeholder=\"Search…\" aria-label=\"Search the site\" autocomplete=\"off\" />
+<ul class=\"search-results\" hidden></ul>
+</form>
+";
+
+/// Settings for the 404 page.
+#[derive(Debug, Clone)]
+pub struct NotFoundConfig {
+    /// Markdown (`.md`) or HTML file used as the page body instead of the built-in text
+    pub source: Option<String>,
+    /// Whether to add a search box below the text
+    pub search: bool,
+    /// Whether to list the projects below the text
+    pub projects: bool,
+}
+
+impl Default for NotFoundConfig {
+    fn default() -> Self {
+        Self {
+            source: None,
+            search: true,
+            projects: true,
+        }
+    }
+}
+
+/// Creates the body of the 404 page.
+///
+/// # Arguments
+/// * `config` - The 404 page settings
+/// * `projects` - The processed projects, listed when enabled
+///
+/// # Returns
+/// * `Result<String>` - The page body HTML, or an error if the source file can't be read
+pub fn create_not_found_content(config: &NotFoundConfig, projects: &[Project]) -> Result<String> {
+    let mut content = match &config.source {
+        Some(path) => {
+            let source = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read 404 page {}: {}", path, e))?;
+            if path.ends_with(".md") {
+                markdown::to_html(frontmatter::split(&source).1)
+            } else {
+                source
+            }
+        }
+        None => DEFAULT_CONTENT.to_string(),
+    };
+
+    if config.search {
+        content.push_str(SEARCH_BOX);
+    }
+    if config.projects && !projects.is_empty() {
+        content.push_str("<h2>Projects</h2>\n");
+        content.push_str(&html::create_project_grid(&project::sort_for_listing(
+            projects,
+        )));
+    }
+    Ok(html::add_heading_ids(&content))
+}
+
+/// Writes `404.html` to the site root.
+///
+/// # Arguments
+/// * `output_dir` - The site root, e.g. `docs`
+/// * `site` - The site settings
+/// * `layout` - The HTML layout template
+/// * `projects` - The processed projects
+pub fn write_not_found_page(
+    output_dir: &str,
+    site: &Site,
+    layout: &str,
+    projects: &[Project],
+) -> Result<()> {
+    let content = create_not_found_content(&site.not_found, projects)?;
+    let meta = PageMeta {
+        title: site.page_title(Some("Page not found")),
+        description: site.description.clone(),
+        canonical_url: site.absolute_url("/404.html"),
+        image_url: site.absolute_url(&format!("/{}", social::CARD_FILENAME)),
+        structured_data: structured_data::create_site_json_ld(site),
+    };
+    let page_layout = templates::apply_page_meta(layout, &meta);
+    let page = templates::process_template(&page_layout, &content, &site.menu_html("/404.html"));
+
+    let output_file = format!("{}/404.html", output_dir);
+    fs::write(&output_file, page)
+        .map_err(|e| format!("Failed to write output file {}: {}", output_file, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_create_not_found_content() {
+        let mut reservoir = Project::new("../r".to_string(), "docs/projects/reservoir".to_string());
+        reservoir.title = Some("Reservoir".to_string());
+        let projects = [reservoir];
+
+        let content = create_not_found_content(&NotFoundConfig::default(), &projects).unwrap();
+        assert!(content.starts_with("<h1 id=\"page-not-found\">Page not found</h1>"));
+        assert!(content.contains("<form class=\"search\" role=\"search\""));
+        assert!(content.contains("<a href=\"/projects/reservoir/\">Reservoir</a>"));
+
+        let dir = TempDir::new("404");
+        let path = dir.join("404.md");
+        fs::write(&path, "---\ntitle: Lost\n---\n# Lost in space\n").unwrap();
+        let config = NotFoundConfig {
+            source: Some(path.to_string_lossy().into_owned()),
+            search: false,
+            projects: false,
+        };
+        assert_eq!(
+            create_not_found_content(&config, &projects).unwrap(),
+            "<h1 id=\"lost-in-space\">Lost in space</h1>\n"
+        );
+    }
+}
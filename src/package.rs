@@ -0,0 +1,463 @@
+//! Package metadata from a project's `Cargo.toml`, `package.json` or `pyproject.toml`.
+//!
+//! Only the handful of fields the site uses are read: description, version,
+//! license, repository and keywords. The TOML and JSON readers are small and
+//! forgiving; anything they don't understand is skipped rather than failing
+//! the build.
+
+/// Package manifests, checked in order.
+pub const PACKAGE_FILES: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// Metadata read from a package manifest.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PackageMetadata {
+    /// One-line package description
+    pub description: Option<String>,
+    /// Package version, e.g. `0.4.2`
+    pub version: Option<String>,
+    /// License expression, e.g. `MIT OR Apache-2.0`
+    pub license: Option<String>,
+    /// Repository URL
+    pub repository: Option<String>,
+    /// Package keywords
+    pub keywords: Vec<String>,
+}
+
+/// Parses a package manifest.
+///
+/// # Arguments
+/// * `file` - The manifest's file name, one of `PACKAGE_FILES`
+/// * `text` - The manifest contents
+///
+/// # Returns
+/// * `Option<PackageMetadata>` - The metadata, or None for unknown or unreadable manifests
+pub fn parse(file: &str, text: &str) -> Option<PackageMetadata> {
+    match file {
+        "Cargo.toml" => Some(parse_toml(text, &["package"], None)),
+        // PEP 621 metadata, then Poetry's own table
+        "pyproject.toml" => Some(parse_toml(
+            text,
+            &["project", "tool.poetry"],
+            Some("project.urls"),
+        )),
+        "package.json" => parse_package_json(text),
+        _ => None,
+    }
+}
+
+/// A TOML value the reader understands.
+#[derive(Debug, Clone, PartialEq)]
+enum TomlValue {
+    String(String),
+    Array(Vec<String>),
+    /// Inline table such as `license = { text = "MIT" }`
+    Table(Vec<(String, String)>),
+}
+
+/// Reads the package fields from the first of `tables` that is present.
+///
+/// `urls_table` holds project links, where a `Repository` or `Source` entry
+/// stands in for a missing `repository` field.
+fn parse_toml(text: &str, tables: &[&str], urls_table: Option<&str>) -> PackageMetadata {
+    let entries = toml_entries(text);
+    let Some(table) = tables
+        .iter()
+        .find(|table| entries.iter().any(|(name, _, _)| name == *table))
+    else {
+        return PackageMetadata::default();
+    };
+    let get = |key: &str| {
+        entries
+            .iter()
+            .find(|(name, k, _)| name == table && k == key)
+            .map(|(_, _, value)| value)
+    };
+    let text = |key: &str| match get(key)? {
+        TomlValue::String(value) => Some(value.clone()),
+        // PEP 621 `license = { text = "MIT" }`
+        TomlValue::Table(fields) => fields
+            .iter()
+            .find(|(k, _)| k == "text")
+            .map(|(_, value)| value.clone()),
+        TomlValue::Array(_) => None,
+    };
+    let repository = text("repository").or_else(|| {
+        entries.iter().find_map(|(name, key, value)| match value {
+            TomlValue::String(url)
+                if Some(name.as_str()) == urls_table
+                    && matches!(key.to_lowercase().as_str(), "repository" | "source") =>
+            {
+                Some(url.clone())
+            }
+            _ => None,
+        })
+    });
+    PackageMetadata {
+        description: text("description"),
+        version: text("version"),
+        license: text("license"),
+        repository,
+        keywords: match get("keywords") {
+            Some(TomlValue::Array(items)) => items.clone(),
+            _ => Vec::new(),
+        },
+    }
+}
+
+/// Splits TOML into `(table, key, value)` entries, skipping anything unsupported
+/// such as dotted keys (`version.workspace = true`) and non-string values.
+fn toml_entries(text: &str) -> Vec<(String, String, TomlValue)> {
+    let mut entries = Vec::new();
+    let mut table = String::new();
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('[') {
+            table = name
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().trim_matches('"').to_string();
+        let mut value = value.trim().to_string();
+        // Multi-line strings and arrays continue until they are closed
+        if value.starts_with("\"\"\"") && !value[3..].contains("\"\"\"") {
+            for next in lines.by_ref() {
+                value.push('\n');
+                value.push_str(next);
+                if next.contains("\"\"\"") {
+                    break;
+                }
+            }
+        } else if value.starts_with('[') && !value.contains(']') {
+            for next in lines.by_ref() {
+                value.push_str(next.trim());
+                if next.contains(']') {
+                    break;
+                }
+            }
+        }
+        if key.contains('.') {
+            continue;
+        }
+        if let Some(value) = toml_value(&value) {
+            entries.push((table.clone(), key, value));
+        }
+    }
+    entries
+}
+
+/// Parses a TOML value; only strings, string arrays and inline tables of strings are kept.
+fn toml_value(value: &str) -> Option<TomlValue> {
+    if let Some(rest) = value.strip_prefix("\"\"\"") {
+        let body = rest.split("\"\"\"").next().unwrap_or_default();
+        // A backslash at the end of a line joins it with the next
+        let body = body
+            .trim_start_matches('\n')
+            .split("\\\n")
+            .map(str::trim_start)
+            .collect::<String>();
+        return Some(TomlValue::String(body.trim_end().to_string()));
+    }
+    if value.starts_with('"') || value.starts_with('\'') {
+        return toml_string(value).map(|(text, _)| TomlValue::String(text));
+    }
+    if let Some(rest) = value.strip_prefix('[') {
+        let mut items = Vec::new();
+        let mut rest = rest.trim_start();
+        while let Some((item, after)) = toml_string(rest) {
+            items.push(item);
+            rest = after.trim_start().trim_start_matches(',').trim_start();
+        }
+        return Some(TomlValue::Array(items));
+    }
+    if let Some(rest) = value.strip_prefix('{') {
+        let fields = rest
+            .trim_end_matches('}')
+            .split(',')
+            .filter_map(|field| {
+                let (key, value) = field.split_once('=')?;
+                let (value, _) = toml_string(value.trim())?;
+                Some((key.trim().trim_matches('"').to_string(), value))
+            })
+            .collect();
+        return Some(TomlValue::Table(fields));
+    }
+    None
+}
+
+/// Reads a basic (`"..."`) or literal (`'...'`) string from the start of `text`.
+///
+/// # Returns
+/// * `Option<(String, &str)>` - The string and the text after it
+fn toml_string(text: &str) -> Option<(String, &str)> {
+    let quote = text.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        match c {
+            c if c == quote => return Some((value, &text[index + 1..])),
+            '\\' if quote == '"' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                other => value.push(other),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// A JSON value, keeping only what `package.json` needs.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+    /// Numbers, booleans and null
+    Other,
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the package fields from `package.json`.
+fn parse_package_json(text: &str) -> Option<PackageMetadata> {
+    let mut chars = text.chars().peekable();
+    let json = json_value(&mut chars)?;
+    let text = |key: &str| json.get(key).and_then(Json::as_str).map(str::to_string);
+    // `repository` is a URL, a `github:owner/repo` shorthand or an object with a `url`
+    let repository = json
+        .get("repository")
+        .and_then(|repository| {
+            repository
+                .as_str()
+                .or_else(|| repository.get("url")?.as_str())
+        })
+        .map(|url| {
+            let url = url.strip_prefix("git+").unwrap_or(url);
+            let shorthand = url.strip_prefix("github:").unwrap_or(url);
+            if !shorthand.contains(':') && shorthand.matches('/').count() == 1 {
+                format!("https://github.com/{}", shorthand)
+            } else {
+                url.to_string()
+            }
+        });
+    Some(PackageMetadata {
+        description: text("description"),
+        version: text("version"),
+        license: text("license"),
+        repository,
+        keywords: match json.get("keywords") {
+            Some(Json::Array(items)) => items
+                .iter()
+                .filter_map(Json::as_str)
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        },
+    })
+}
+
+/// Parses one JSON value, returning None on malformed input.
+fn json_value(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<Json> {
+    skip_whitespace(chars);
+    match *chars.peek()? {
+        '"' => json_string(chars).map(Json::String),
+        '[' => {
+            chars.next();
+            let mut items = Vec::new();
+            loop {
+                skip_whitespace(chars);
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                    return Some(Json::Array(items));
+                }
+                items.push(json_value(chars)?);
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => continue,
+                    ']' => return Some(Json::Array(items)),
+                    _ => return None,
+                }
+            }
+        }
+        '{' => {
+            chars.next();
+            let mut fields = Vec::new();
+            loop {
+                skip_whitespace(chars);
+                if chars.peek() == Some(&'}') {
+                    chars.next();
+                    return Some(Json::Object(fields));
+                }
+                let key = json_string(chars)?;
+                skip_whitespace(chars);
+                if chars.next()? != ':' {
+                    return None;
+                }
+                fields.push((key, json_value(chars)?));
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => continue,
+                    '}' => return Some(Json::Object(fields)),
+                    _ => return None,
+                }
+            }
+        }
+        _ => {
+            while chars
+                .peek()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+            {
+                chars.next();
+            }
+            Some(Json::Other)
+        }
+    }
+}
+
+/// Parses a JSON string literal, starting at its opening quote.
+fn json_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+    if chars.next()? != '"' {
+        return None;
+    }
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                'b' => value.push('\u{8}'),
+                'f' => value.push('\u{c}'),
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                other => value.push(other),
+            },
+            c => value.push(c),
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_toml() {
+        let cargo = r#"[package]
+name = "reservoir"
+version = "0.4.2"
+edition.workspace = true
+description = "Memory for \"LLM\" chats"
+license = 'MIT OR Apache-2.0'
+repository = "https://github.com/Sector-F-Labs/reservoir"
+keywords = [
+    "llm",
+    "memory",
+]
+
+[dependencies]
+description = "not this one"
+"#;
+        assert_eq!(
+            parse("Cargo.toml", cargo),
+            Some(PackageMetadata {
+                description: Some("Memory for \"LLM\" chats".to_string()),
+                version: Some("0.4.2".to_string()),
+                license: Some("MIT OR Apache-2.0".to_string()),
+                repository: Some("https://github.com/Sector-F-Labs/reservoir".to_string()),
+                keywords: vec!["llm".to_string(), "memory".to_string()],
+            })
+        );
+        assert_eq!(
+            parse("Cargo.toml", "[workspace]\nmembers = [\"a\"]\n"),
+            Some(PackageMetadata::default())
+        );
+    }
+
+    #[test]
+    fn test_parse_pyproject_toml() {
+        let pyproject = r#"[project]
+name = "md-chat"
+version = "1.0.0"
+description = """
+Chat in markdown"""
+license = { text = "MIT" }
+keywords = ["chat", "markdown"]
+
+[project.urls]
+Homepage = "https://sectorflabs.com"
+Repository = "https://github.com/Sector-F-Labs/md-chat"
+"#;
+        let meta = parse("pyproject.toml", pyproject).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Chat in markdown"));
+        assert_eq!(meta.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            meta.repository.as_deref(),
+            Some("https://github.com/Sector-F-Labs/md-chat")
+        );
+        assert_eq!(meta.keywords, vec!["chat", "markdown"]);
+    }
+
+    #[test]
+    fn test_parse_package_json() {
+        let package = r#"{
+  "name": "service-pipe",
+  "version": "0.1.0",
+  "private": true,
+  "description": "Pipes & services",
+  "keywords": ["pipes", 1, "services"],
+  "repository": { "type": "git", "url": "git+https://github.com/Sector-F-Labs/service-pipe.git" },
+  "scripts": { "test": "node test.js" }
+}"#;
+        let meta = parse("package.json", package).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Pipes & services"));
+        assert_eq!(meta.version.as_deref(), Some("0.1.0"));
+        assert_eq!(
+            meta.repository.as_deref(),
+            Some("https://github.com/Sector-F-Labs/service-pipe.git")
+        );
+        assert_eq!(meta.keywords, vec!["pipes", "services"]);
+
+        let shorthand = parse(
+            "package.json",
+            r#"{"repository": "github:Sector-F-Labs/a"}"#,
+        );
+        assert_eq!(
+            shorthand.unwrap().repository.as_deref(),
+            Some("https://github.com/Sector-F-Labs/a")
+        );
+        assert_eq!(parse("package.json", "{\"name\": "), None);
+    }
+}
@@ -0,0 +1,109 @@
+//! A small scoped worker pool for running build jobs in parallel.
+//!
+//! Jobs are handed out in order to `jobs` threads, and results come back in
+//! the same order as the input regardless of which thread finished first, so
+//! output files and log lines stay deterministic.
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Number of workers to use when `--jobs` isn't given: one per available CPU.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+}
+
+/// Runs `job` on every item using up to `jobs` threads.
+///
+/// # Arguments
+/// * `jobs` - Maximum number of worker threads; 1 runs everything on the calling thread
+/// * `items` - The items to process, mutated in place
+/// * `job` - The work to do for each item
+///
+/// # Returns
+/// * `Vec<R>` - One result per item, in input order
+///
+/// # Examples
+/// ```
+/// let mut numbers = vec![1, 2, 3];
+/// let doubled = for_each_mut(4, &mut numbers, |n| *n * 2);
+/// assert_eq!(doubled, vec![2, 4, 6]);
+/// ```
+pub fn for_each_mut<T, R, F>(jobs: usize, items: &mut [T], job: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(&mut T) -> R + Sync,
+{
+    let workers = jobs.clamp(1, items.len().max(1));
+    if workers == 1 {
+        return items.iter_mut().map(job).collect();
+    }
+
+    let slots: Vec<Mutex<(&mut T, Option<R>)>> = items
+        .iter_mut()
+        .map(|item| Mutex::new((item, None)))
+        .collect();
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some(slot) = slots.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let mut slot = slot.lock().unwrap();
+                    let result = job(&mut *slot.0);
+                    slot.1 = Some(result);
+                }
+            });
+        }
+    });
+    slots
+        .into_iter()
+        .map(|slot| slot.into_inner().unwrap().1.unwrap())
+        .collect()
+}
+
+/// Runs `job` on every item of a read-only slice using up to `jobs` threads.
+///
+/// # Arguments
+/// * `jobs` - Maximum number of worker threads
+/// * `items` - The items to process
+/// * `job` - The work to do for each item
+///
+/// # Returns
+/// * `Vec<R>` - One result per item, in input order
+pub fn map<T, R, F>(jobs: usize, items: &[T], job: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let mut refs: Vec<&T> = items.iter().collect();
+    for_each_mut(jobs, &mut refs, |item| job(item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_each_mut_keeps_order() {
+        let mut items: Vec<u64> = (0..50).collect();
+        // Earlier items sleep longer so they finish last
+        let results = for_each_mut(8, &mut items, |n| {
+            thread::sleep(std::time::Duration::from_millis(50 - *n));
+            *n += 100;
+            *n
+        });
+        assert_eq!(results, (100..150).collect::<Vec<u64>>());
+        assert_eq!(items, results);
+    }
+
+    #[test]
+    fn test_map_single_job_and_empty() {
+        assert_eq!(map(1, &["a", "bb"], |s| s.len()), vec![1, 2]);
+        assert_eq!(map(4, &[] as &[&str], |s| s.len()), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,197 @@
+//! Redirect stubs for pages that moved.
+//!
+//! GitHub Pages can't send HTTP redirects, so each old URL gets a small HTML
+//! page with a `meta refresh` to the new location and a canonical link, which
+//! search engines treat like a permanent redirect.
+
+use crate::html;
+use crate::site::Site;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Marker identifying generated stubs, so they can be overwritten on later builds.
+const STUB_MARKER: &str = "<meta http-equiv=\"refresh\"";
+
+/// An old URL that forwards to a new one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redirect {
+    /// Site-relative path of the old page, e.g. `/projects/exp-013-service-pipe/`
+    pub from: String,
+    /// Site-relative path or absolute URL of the new page
+    pub to: String,
+}
+
+impl Redirect {
+    /// Creates a new Redirect.
+    ///
+    /// # Arguments
+    /// * `from` - The old path; a missing leading slash is added
+    /// * `to` - The new path or absolute URL
+    ///
+    /// # Returns
+    /// * `Redirect` - A new redirect
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: format!("/{}", from.trim_start_matches('/')),
+            to: to.to_string(),
+        }
+    }
+
+    /// Whether the redirect points off-site.
+    fn is_external(&self) -> bool {
+        self.to.contains("://") || self.to.starts_with("//")
+    }
+}
+
+/// Creates the HTML of a redirect stub.
+///
+/// # Arguments
+/// * `site` - The site settings, used to make the canonical link absolute
+/// * `to` - The site-relative path or absolute URL to forward to
+///
+/// # Returns
+/// * `String` - A complete HTML document
+pub fn create_redirect_page(site: &Site, to: &str) -> String {
+    let canonical = if to.starts_with('/') && !to.starts_with("//") {
+        site.absolute_url(to)
+    } else {
+        to.to_string()
+    };
+    let to = html::escape(to);
+    let canonical = html::escape(&canonical);
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Redirecting to {canonical}</title>
+<link rel=\"canonical\" href=\"{canonical}\">
+<meta name=\"robots\" content=\"noindex\">
+{STUB_MARKER} content=\"0; url={to}\">
+</head>
+<body>
+<p>This page has moved to <a href=\"{to}\">{canonical}</a>.</p>
+</body>
+</html>
+"
+    )
+}
+
+/// Maps a site-relative path to the file serving it, e.g. `/a/` to `docs/a/index.html`.
+fn output_file(output_dir: &str, path: &str) -> PathBuf {
+    let path = path.split(['#', '?']).next().unwrap_or_default();
+    let file = Path::new(output_dir).join(path.trim_start_matches('/'));
+    if path.ends_with('/') || file.is_dir() || file.extension().is_none() {
+        file.join("index.html")
+    } else {
+        file
+    }
+}
+
+/// Writes a stub for every redirect and checks that each target exists.
+///
+/// Call this after all pages are generated so targets can be checked.
+///
+/// # Arguments
+/// * `output_dir` - The site root, e.g. `docs`
+/// * `site` - The site settings
+/// * `redirects` - The redirects from config and front matter `aliases`
+///
+/// # Returns
+/// * `Result<usize>` - The number of stubs written
+///
+/// # Errors
+/// Returns an error if two redirects share an old path, a stub would overwrite a
+/// generated page, or a target is missing from the output.
+pub fn write_redirects(output_dir: &str, site: &Site, redirects: &[Redirect]) -> Result<usize> {
+    let mut seen = HashSet::new();
+    for redirect in redirects {
+        if !seen.insert(&redirect.from) {
+            return Err(format!("Redirect {} is defined more than once", redirect.from).into());
+        }
+        let file = output_file(output_dir, &redirect.from);
+        if let Ok(existing) = fs::read_to_string(&file)
+            && !existing.contains(STUB_MARKER)
+        {
+            return Err(format!(
+                "Redirect {} would overwrite the page {}",
+                redirect.from,
+                file.display()
+            )
+            .into());
+        }
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        fs::write(&file, create_redirect_page(site, &redirect.to))
+            .map_err(|e| format!("Failed to write redirect {}: {}", file.display(), e))?;
+    }
+
+    let missing: Vec<String> = redirects
+        .iter()
+        .filter(|redirect| {
+            !redirect.is_external() && !output_file(output_dir, &redirect.to).is_file()
+        })
+        .map(|redirect| format!("{} -> {}", redirect.from, redirect.to))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Redirect targets not found: {}", missing.join(", ")).into());
+    }
+    Ok(redirects.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_create_redirect_page() {
+        let site = Site::new("Sector F Labs", "https://sectorflabs.com", "");
+        let page = create_redirect_page(&site, "/experiments/pipe/");
+        assert!(
+            page.contains("<meta http-equiv=\"refresh\" content=\"0; url=/experiments/pipe/\">")
+        );
+        assert!(page.contains(
+            "<link rel=\"canonical\" href=\"https://sectorflabs.com/experiments/pipe/\">"
+        ));
+        let page = create_redirect_page(&site, "https://example.com/?a=1&b=2");
+        assert!(page.contains("href=\"https://example.com/?a=1&amp;b=2\""));
+    }
+
+    #[test]
+    fn test_write_redirects() {
+        let root = TempDir::new("redirects");
+        let output = root.to_str().unwrap();
+        let site = Site::new("Sector F Labs", "https://sectorflabs.com", "");
+        fs::create_dir_all(root.join("experiments/pipe")).unwrap();
+        fs::write(root.join("experiments/pipe/index.html"), "<p>pipe</p>").unwrap();
+
+        let redirects = [
+            Redirect::new("projects/pipe/", "/experiments/pipe/"),
+            Redirect::new("/old.html", "/experiments/pipe/#usage"),
+            Redirect::new("/gone/", "https://example.com/"),
+        ];
+        assert_eq!(write_redirects(output, &site, &redirects).unwrap(), 3);
+        assert!(
+            fs::read_to_string(root.join("projects/pipe/index.html"))
+                .unwrap()
+                .contains("url=/experiments/pipe/")
+        );
+        assert!(root.join("old.html").is_file());
+        // Rebuilding overwrites earlier stubs
+        assert!(write_redirects(output, &site, &redirects).is_ok());
+
+        let missing = [Redirect::new("/a/", "/nowhere/")];
+        assert!(write_redirects(output, &site, &missing).is_err());
+        let clobber = [Redirect::new("/experiments/pipe/", "/old.html")];
+        assert!(write_redirects(output, &site, &clobber).is_err());
+        let duplicate = [redirects[0].clone(), redirects[0].clone()];
+        assert!(write_redirects(output, &site, &duplicate).is_err());
+    }
+}
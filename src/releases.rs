@@ -0,0 +1,275 @@
+//! Release history pages built from git tags and `CHANGELOG.md`.
+//!
+//! Each tag becomes an entry with its date and message, followed by the
+//! matching section of a Keep-a-Changelog formatted `CHANGELOG.md` when the
+//! project has one, and links to the tag and the changes since the previous
+//! tag on the forge.
+
+use crate::git::{self, Tag};
+use crate::html;
+use crate::navigation;
+use crate::project::{Project, SiteLink};
+use crate::site::Site;
+use crate::templates;
+use regex::Regex;
+use std::error::Error;
+use std::fs;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Path of the release history page relative to the project page.
+pub const RELEASES_PATH: &str = "releases/";
+
+/// A version section of a Keep-a-Changelog file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogSection {
+    /// Version as written in the heading, e.g. `1.2.0` or `Unreleased`
+    pub version: String,
+    /// Release date from the heading, if any
+    pub date: Option<String>,
+    /// Markdown body of the section
+    pub body: String,
+}
+
+/// Splits a Keep-a-Changelog file into its version sections.
+///
+/// Recognises `## [1.2.0] - 2026-10-01` and `## 1.2.0 - 2026-10-01` headings,
+/// and drops the link reference definitions usually kept at the end.
+///
+/// # Arguments
+/// * `markdown` - The contents of `CHANGELOG.md`
+///
+/// # Returns
+/// * `Vec<ChangelogSection>` - The sections in file order
+pub fn parse_changelog(markdown: &str) -> Vec<ChangelogSection> {
+    let heading = Regex::new(r"^##\s+\[?([^\]\s]+)\]?(?:\s+-\s+(\S+))?").unwrap();
+    let reference = Regex::new(r"^\[[^\]]+\]:\s").unwrap();
+
+    let mut sections: Vec<ChangelogSection> = Vec::new();
+    for line in markdown.lines() {
+        if let Some(caps) = heading.captures(line) {
+            sections.push(ChangelogSection {
+                version: caps[1].to_string(),
+                date: caps.get(2).map(|date| date.as_str().to_string()),
+                body: String::new(),
+            });
+        } else if let Some(section) = sections.last_mut()
+            && !reference.is_match(line)
+        {
+            section.body.push_str(line);
+            section.body.push('\n');
+        }
+    }
+    for section in &mut sections {
+        section.body = section.body.trim().to_string();
+    }
+    sections
+}
+
+/// Finds the changelog section for a tag, ignoring a leading `v`.
+fn find_section<'a>(sections: &'a [ChangelogSection], tag: &str) -> Option<&'a ChangelogSection> {
+    let version = tag.trim_start_matches('v');
+    sections
+        .iter()
+        .find(|section| section.version.trim_start_matches('v') == version)
+}
+
+/// Creates the body of a project's release history page.
+///
+/// # Arguments
+/// * `project` - The project, used for its title and forge links
+/// * `tags` - The project's tags, newest first
+/// * `changelog` - The parsed `CHANGELOG.md`, or an empty slice
+///
+/// # Returns
+/// * `String` - The page body HTML
+pub fn create_releases_content(
+    project: &Project,
+    tags: &[Tag],
+    changelog: &[ChangelogSection],
+) -> String {
+    let remote = project.remote();
+    let title = html::escape(project.display_title());
+    let mut content = format!(
+        "<h1>{} releases</h1>\n<p>Every tagged version of <a href=\"../\">{}</a>, newest first.</p>\n",
+        title, title
+    );
+
+    let unreleased = changelog
+        .iter()
+        .find(|section| section.version.eq_ignore_ascii_case("unreleased"))
+        .filter(|section| !section.body.is_empty());
+    if let Some(section) = unreleased {
+        let compare = tags.first().zip(remote.as_ref()).and_then(|(tag, remote)| {
+            remote.compare_url(
+                &tag.name,
+                project.default_branch.as_deref().unwrap_or("HEAD"),
+            )
+        });
+        content.push_str("<h2>Unreleased</h2>\n");
+        if let Some(url) = compare {
+            content.push_str(&format!(
+                "<p class=\"release-meta\"><a href=\"{}\">Changes since {}</a></p>\n",
+                html::escape(&url),
+                html::escape(&tags[0].name)
+            ));
+        }
+        content.push_str(&markdown::to_html(&section.body));
+        content.push('\n');
+    }
+
+    for (index, tag) in tags.iter().enumerate() {
+        let date = tag.date.get(..10).unwrap_or(&tag.date);
+        let mut meta = vec![format!(
+            "<time datetime=\"{0}\">{0}</time>",
+            html::escape(date)
+        )];
+        if let Some(url) = remote.as_ref().and_then(|remote| remote.tag_url(&tag.name)) {
+            meta.push(format!("<a href=\"{}\">Tag</a>", html::escape(&url)));
+        }
+        let compare = tags
+            .get(index + 1)
+            .zip(remote.as_ref())
+            .and_then(|(previous, remote)| {
+                remote
+                    .compare_url(&previous.name, &tag.name)
+                    .map(|url| (previous, url))
+            });
+        if let Some((previous, url)) = compare {
+            meta.push(format!(
+                "<a href=\"{}\">Changes since {}</a>",
+                html::escape(&url),
+                html::escape(&previous.name)
+            ));
+        }
+
+        content.push_str(&format!("<h2>{}</h2>\n", html::escape(&tag.name)));
+        content.push_str(&format!(
+            "<p class=\"release-meta\">{}</p>\n",
+            meta.join(" · ")
+        ));
+        if !tag.message.is_empty() && tag.message != tag.name {
+            content.push_str(&format!("<p>{}</p>\n", html::escape(&tag.message)));
+        }
+        if let Some(section) = find_section(changelog, &tag.name) {
+            content.push_str(&markdown::to_html(&section.body));
+            content.push('\n');
+        }
+    }
+    html::add_heading_ids(&content)
+}
+
+/// Writes a project's release history page if it has any tags or changelog entries.
+///
+/// # Arguments
+/// * `project` - The processed project
+/// * `site` - The site settings used for page metadata
+/// * `layout` - The HTML layout template
+///
+/// # Returns
+/// * `Result<Option<String>>` - The site-relative URL of the page, or None if there was nothing to list
+pub fn write_releases_page(project: &Project, site: &Site, layout: &str) -> Result<Option<String>> {
+    let tags = git::get_tags(&project.source_dir);
+    // Bare repositories have no working tree, so fall back to the committed file
+    let changelog = fs::read_to_string(format!("{}/CHANGELOG.md", project.source_dir))
+        .ok()
+        .or_else(|| {
+            git::read_blob(&project.source_dir, "HEAD", "CHANGELOG.md")
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+        })
+        .map(|markdown| parse_changelog(&markdown))
+        .unwrap_or_default();
+    if tags.is_empty() && changelog.is_empty() {
+        return Ok(None);
+    }
+
+    let url = format!("{}{}", project.url(), RELEASES_PATH);
+    let mut meta = project.page_meta(site);
+    meta.title = site.page_title(Some(&format!("{} releases", project.display_title())));
+    meta.description = format!("Release history of {}", project.display_title());
+    meta.canonical_url = site.absolute_url(&url);
+
+    let mut trail = project.breadcrumbs.clone();
+    trail.push(SiteLink::new("Release history".to_string(), url.clone()));
+    let content = format!(
+        "{}{}{}",
+        navigation::create_breadcrumbs_html(&trail),
+        navigation::create_breadcrumbs_json_ld(site, &trail),
+        create_releases_content(project, &tags, &changelog)
+    );
+    let page_layout = templates::apply_page_meta(layout, &meta);
+    let page = templates::process_template(&page_layout, &content, &site.menu_html(&url));
+
+    let output_dir = format!("{}/{}", project.output_dir, RELEASES_PATH);
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create output directory {}: {}", output_dir, e))?;
+    let output_file = format!("{}index.html", output_dir);
+    fs::write(&output_file, page)
+        .map_err(|e| format!("Failed to write output file {}: {}", output_file, e))?;
+    Ok(Some(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGELOG: &str = "# Changelog
+
+## [Unreleased]
+### Added
+- Streaming
+
+## [0.2.0] - 2026-09-01
+### Fixed
+- Crash on empty input
+
+## 0.1.0 - 2026-01-01
+- First release
+
+[Unreleased]: https://github.com/o/r/compare/v0.2.0...HEAD
+[0.2.0]: https://github.com/o/r/compare/v0.1.0...v0.2.0
+";
+
+    fn tag(name: &str, date: &str, message: &str) -> Tag {
+        Tag {
+            name: name.to_string(),
+            date: date.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_changelog() {
+        let sections = parse_changelog(CHANGELOG);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].version, "Unreleased");
+        assert_eq!(sections[1].version, "0.2.0");
+        assert_eq!(sections[1].date.as_deref(), Some("2026-09-01"));
+        assert_eq!(sections[1].body, "### Fixed\n- Crash on empty input");
+        assert_eq!(sections[2].body, "- First release");
+        assert_eq!(find_section(&sections, "v0.1.0"), Some(&sections[2]));
+    }
+
+    #[test]
+    fn test_create_releases_content() {
+        let mut project = Project::new("../r".to_string(), "docs/projects/r".to_string());
+        project.title = Some("R".to_string());
+        project.github_url = Some("https://github.com/o/r".to_string());
+        project.default_branch = Some("main".to_string());
+        let tags = [
+            tag("v0.2.0", "2026-09-01T10:00:00+00:00", "Fixes"),
+            tag("v0.1.0", "2026-01-01T10:00:00+00:00", "v0.1.0"),
+        ];
+        let content = create_releases_content(&project, &tags, &parse_changelog(CHANGELOG));
+
+        assert!(content.contains("<h1 id=\"r-releases\">R releases</h1>"));
+        assert!(content.contains(
+            "<a href=\"https://github.com/o/r/compare/v0.2.0...main\">Changes since v0.2.0</a>"
+        ));
+        assert!(content.contains("<h2 id=\"v020\">v0.2.0</h2>\n<p class=\"release-meta\"><time datetime=\"2026-09-01\">2026-09-01</time> · <a href=\"https://github.com/o/r/releases/tag/v0.2.0\">Tag</a> · <a href=\"https://github.com/o/r/compare/v0.1.0...v0.2.0\">Changes since v0.1.0</a></p>\n<p>Fixes</p>"));
+        assert!(content.contains("<li>Crash on empty input</li>"));
+        // Messages that just repeat the tag name are skipped
+        assert!(!content.contains("<p>v0.1.0</p>"));
+        assert!(content.contains("<li>First release</li>"));
+    }
+}
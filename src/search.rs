@@ -0,0 +1,159 @@
+//! Build-time search index for the client-side search box.
+//!
+//! The index is a single JSON file, `search-index.json`, loaded on demand by
+//! `search.js`. Each page is stored as a compact array whose field order is
+//! declared once in the header, so the file stays small as pages are added.
+
+use crate::html;
+use regex::Regex;
+
+/// Version of the index format; bump when `FIELDS` or their encoding change.
+pub const INDEX_VERSION: u32 = 1;
+
+/// Field order of each page array in the index.
+const FIELDS: [&str; 5] = ["title", "url", "headings", "tags", "body"];
+
+/// A single page in the search index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchDocument {
+    /// Page title
+    pub title: String,
+    /// Site-relative page URL
+    pub url: String,
+    /// Text of every heading on the page
+    pub headings: Vec<String>,
+    /// Page tags
+    pub tags: Vec<String>,
+    /// Plain body text with whitespace collapsed
+    pub body: String,
+}
+
+impl SearchDocument {
+    /// Builds a search document from a rendered page body.
+    ///
+    /// # Arguments
+    /// * `title` - The page title
+    /// * `url` - The site-relative page URL
+    /// * `tags` - The page tags
+    /// * `content` - The page body HTML, without the layout
+    ///
+    /// # Returns
+    /// * `SearchDocument` - The document with headings and text extracted
+    pub fn from_html(title: &str, url: &str, tags: &[String], content: &str) -> Self {
+        // Drop code copy buttons so "Copy" doesn't end up in every body
+        let button = Regex::new(r"(?s)<button[^>]*>.*?</button>").unwrap();
+        let content = button.replace_all(content, " ");
+
+        let heading = Regex::new(r"(?s)<h[1-6][^>]*>(.*?)</h[1-6]>").unwrap();
+        let headings = heading
+            .captures_iter(&content)
+            .map(|caps| html::strip_tags(&caps[1]))
+            .filter(|text| !text.is_empty())
+            .collect();
+
+        // Keep words from adjacent block elements apart
+        let block = Regex::new(r"<(/?)(p|h[1-6]|li|div|pre|br|tr|td|th|blockquote)\b").unwrap();
+        let spaced = block.replace_all(&content, " <$1$2");
+        Self {
+            title: title.to_string(),
+            url: url.to_string(),
+            headings,
+            tags: tags.to_vec(),
+            body: html::strip_tags(&spaced),
+        }
+    }
+}
+
+/// Serialises documents into the versioned index JSON.
+///
+/// # Arguments
+/// * `documents` - The pages to index, in output order
+///
+/// # Returns
+/// * `String` - The index JSON
+///
+/// # Examples
+/// ```
+/// let json = create_search_index(&[]);
+/// assert_eq!(json, r#"{"version":1,"fields":["title","url","headings","tags","body"],"pages":[]}"#);
+/// ```
+pub fn create_search_index(documents: &[SearchDocument]) -> String {
+    let fields: Vec<String> = FIELDS.iter().map(|field| json_string(field)).collect();
+    let pages: Vec<String> = documents
+        .iter()
+        .map(|doc| {
+            format!(
+                "[{},{},{},{},{}]",
+                json_string(&doc.title),
+                json_string(&doc.url),
+                json_array(&doc.headings),
+                json_array(&doc.tags),
+                json_string(&doc.body)
+            )
+        })
+        .collect();
+    format!(
+        "{{\"version\":{},\"fields\":[{}],\"pages\":[{}]}}",
+        INDEX_VERSION,
+        fields.join(","),
+        pages.join(",")
+    )
+}
+
+/// Encodes a string as a JSON string literal.
+pub fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            // Keep the output safe to inline inside <script> tags
+            '<' => json.push_str("\\u003c"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Encodes a list of strings as a JSON array.
+fn json_array(items: &[String]) -> String {
+    let items: Vec<String> = items.iter().map(|item| json_string(item)).collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_html() {
+        let content = "<h1>Reservoir</h1><p>Stores <em>chat</em>. History.</p><div class=\"code-block\"><pre><button class=\"copy-btn\">Copy</button><code>cargo run</code></pre></div><h2>Install &amp; run</h2>";
+        let doc = SearchDocument::from_html("Reservoir", "/projects/reservoir/", &[], content);
+        assert_eq!(doc.headings, vec!["Reservoir", "Install & run"]);
+        assert_eq!(
+            doc.body,
+            "Reservoir Stores chat. History. cargo run Install & run"
+        );
+    }
+
+    #[test]
+    fn test_create_search_index() {
+        let doc = SearchDocument {
+            title: "Say \"hi\"".to_string(),
+            url: "/a/".to_string(),
+            headings: vec!["One".to_string()],
+            tags: vec!["rust".to_string()],
+            body: "line\nbreak </script>".to_string(),
+        };
+        assert_eq!(
+            create_search_index(&[doc]),
+            r#"{"version":1,"fields":["title","url","headings","tags","body"],"pages":[["Say \"hi\"","/a/",["One"],["rust"],"line\nbreak \u003c/script>"]]}"#
+        );
+    }
+}
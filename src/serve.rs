@@ -0,0 +1,228 @@
+//! A minimal static file server for previewing the generated site.
+//!
+//! Serves the draft preview in `.site-cache/preview/` the way GitHub Pages
+//! serves `docs/`: directories resolve to their `index.html`, directory URLs
+//! without a trailing slash redirect to one so relative links keep working,
+//! and missing files get `404.html`. It handles one request at a time and is
+//! only meant for local previews.
+
+use crate::url::percent_decode;
+use std::error::Error;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Address the preview server listens on.
+pub const ADDRESS: &str = "127.0.0.1:8000";
+
+/// How a request path maps onto the output directory.
+#[derive(Debug, PartialEq, Eq)]
+enum Resolved {
+    /// Serve this file
+    File(PathBuf),
+    /// Redirect to this path, which adds a trailing slash
+    Redirect(String),
+    /// Nothing there
+    NotFound,
+}
+
+/// Serves `root` until the process is stopped.
+///
+/// # Arguments
+/// * `root` - The site root, e.g. `docs`
+/// * `address` - The address to listen on, usually `ADDRESS`
+pub fn serve(root: &str, address: &str) -> Result<()> {
+    let listener = TcpListener::bind(address)
+        .map_err(|e| format!("Failed to listen on {}: {}", address, e))?;
+    println!(
+        "🌐 Serving {} at http://{}/ (Ctrl+C to stop)",
+        root, address
+    );
+    for stream in listener.incoming().flatten() {
+        if let Err(e) = respond(Path::new(root), stream) {
+            eprintln!("⚠️  Request failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Reads one request and writes the response.
+fn respond(root: &Path, mut stream: TcpStream) -> Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers; nothing in them changes the response
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or("/"),
+    );
+    if method != "GET" && method != "HEAD" {
+        return write_response(
+            &mut stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            &[],
+            b"",
+        );
+    }
+
+    match resolve(root, target) {
+        Resolved::File(file) => {
+            let body = fs::read(&file)?;
+            let body = if method == "HEAD" { &[][..] } else { &body[..] };
+            write_response(&mut stream, "200 OK", content_type(&file), &[], body)
+        }
+        Resolved::Redirect(location) => write_response(
+            &mut stream,
+            "301 Moved Permanently",
+            "text/plain",
+            &[("Location", &location)],
+            b"",
+        ),
+        Resolved::NotFound => {
+            let body = fs::read(root.join("404.html")).unwrap_or_else(|_| b"Not found".to_vec());
+            write_response(
+                &mut stream,
+                "404 Not Found",
+                "text/html; charset=utf-8",
+                &[],
+                &body,
+            )
+        }
+    }
+}
+
+/// Writes an HTTP/1.0 response and closes the connection.
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> Result<()> {
+    let mut head = format!(
+        "HTTP/1.0 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body)?;
+    Ok(())
+}
+
+/// Maps a request target such as `/projects/reservoir/?q=1` to a file under `root`.
+fn resolve(root: &Path, target: &str) -> Resolved {
+    let raw = target.split(['?', '#']).next().unwrap_or_default();
+    let Some(path) = percent_decode(raw) else {
+        return Resolved::NotFound;
+    };
+    let relative = Path::new(path.trim_start_matches('/'));
+    // Never serve anything outside the root
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Resolved::NotFound;
+    }
+
+    let file = root.join(relative);
+    if file.is_dir() {
+        if !raw.ends_with('/') {
+            return Resolved::Redirect(format!("{}/", raw));
+        }
+        let index = file.join("index.html");
+        return if index.is_file() {
+            Resolved::File(index)
+        } else {
+            Resolved::NotFound
+        };
+    }
+    if file.is_file() {
+        Resolved::File(file)
+    } else {
+        Resolved::NotFound
+    }
+}
+
+/// Picks the `Content-Type` for a file from its extension.
+fn content_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+    {
+        "html" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain; charset=utf-8",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_resolve() {
+        let root = TempDir::new("serve");
+        fs::create_dir_all(root.join("projects/r")).unwrap();
+        fs::write(root.join("projects/r/index.html"), "r").unwrap();
+        fs::write(root.join("style.css"), "").unwrap();
+        fs::create_dir_all(root.join("tags/c++ & ü")).unwrap();
+        fs::write(root.join("tags/c++ & ü/index.html"), "t").unwrap();
+
+        assert_eq!(
+            resolve(&root, "/projects/r/?q=1"),
+            Resolved::File(root.join("projects/r/index.html"))
+        );
+        assert_eq!(
+            resolve(&root, "/projects/r"),
+            Resolved::Redirect("/projects/r/".to_string())
+        );
+        assert_eq!(
+            resolve(&root, "/style.css"),
+            Resolved::File(root.join("style.css"))
+        );
+        assert_eq!(resolve(&root, "/projects/"), Resolved::NotFound);
+        assert_eq!(resolve(&root, "/../etc/passwd"), Resolved::NotFound);
+        assert_eq!(
+            resolve(&root, "/tags/c++%20%26%20%C3%BC/"),
+            Resolved::File(root.join("tags/c++ & ü/index.html"))
+        );
+        assert_eq!(
+            resolve(&root, "/tags/c++%20%26%20%C3%BC"),
+            Resolved::Redirect("/tags/c++%20%26%20%C3%BC/".to_string())
+        );
+        // Escaped separators are decoded before the traversal check
+        assert_eq!(resolve(&root, "/%2e%2e/etc/passwd"), Resolved::NotFound);
+        assert_eq!(resolve(&root, "/..%2Fetc%2Fpasswd"), Resolved::NotFound);
+        assert_eq!(resolve(&root, "/bad%zz"), Resolved::NotFound);
+        assert_eq!(content_type(Path::new("a/b.svg")), "image/svg+xml");
+    }
+}
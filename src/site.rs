@@ -0,0 +1,154 @@
+//! Site-wide settings shared by every generated page.
+
+use crate::date::Timestamp;
+use crate::not_found::NotFoundConfig;
+use crate::project::SiteLink;
+use crate::redirects::Redirect;
+use crate::templates;
+use crate::validate::ValidationConfig;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Describes the site being generated.
+///
+/// These values are used for page titles, default descriptions and to turn
+/// site-relative paths into absolute URLs for canonical links and social cards.
+#[derive(Debug, Clone)]
+pub struct Site {
+    /// Site name used in titles, e.g. "Sector F Labs"
+    pub name: String,
+    /// Absolute base URL without a trailing slash, e.g. "https://sectorflabs.com"
+    pub base_url: String,
+    /// Default page description
+    pub description: String,
+    /// Default social image, as a site-relative path
+    pub image: String,
+    /// Whether feed entries carry the full page HTML rather than just a summary
+    pub feed_full_content: bool,
+    /// Whether to publish an RSS 2.0 feed alongside the Atom feed
+    pub feed_rss: bool,
+    /// Severity of each HTML validation rule checked after a build
+    pub validation: ValidationConfig,
+    /// Old URLs that forward to their new location
+    pub redirects: Vec<Redirect>,
+    /// Content of the `404.html` page
+    pub not_found: NotFoundConfig,
+    /// Whether draft and scheduled pages are built, with a banner
+    pub drafts: bool,
+    /// Date of this build, `YYYY-MM-DD`, compared against `publish_date`
+    pub build_date: String,
+    /// Navigation links shown on every page
+    pub menu: Vec<SiteLink>,
+}
+
+impl Site {
+    /// Creates a new Site.
+    ///
+    /// # Arguments
+    /// * `name` - The site name
+    /// * `base_url` - The absolute base URL; a trailing slash is removed
+    /// * `description` - The default page description
+    ///
+    /// # Returns
+    /// * `Site` - A new site using `/logo.png` as the default social image,
+    ///   publishing a full-content Atom feed only, without redirects, drafts or
+    ///   navigation links and with the built-in 404 page
+    pub fn new(name: &str, base_url: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            description: description.to_string(),
+            image: "/logo.png".to_string(),
+            feed_full_content: true,
+            feed_rss: false,
+            validation: ValidationConfig::default(),
+            redirects: Vec::new(),
+            not_found: NotFoundConfig::default(),
+            drafts: false,
+            build_date: today(),
+            menu: Vec::new(),
+        }
+    }
+
+    /// Converts a site-relative path into an absolute URL.
+    ///
+    /// # Arguments
+    /// * `path` - A path such as `/projects/reservoir/`; a missing leading slash is added
+    ///
+    /// # Returns
+    /// * `String` - The absolute URL
+    ///
+    /// # Examples
+    /// ```
+    /// let site = Site::new("Sector F Labs", "https://sectorflabs.com/", "");
+    /// assert_eq!(site.absolute_url("/projects/reservoir/"), "https://sectorflabs.com/projects/reservoir/");
+    /// ```
+    pub fn absolute_url(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            format!("{}{}", self.base_url, path)
+        } else {
+            format!("{}/{}", self.base_url, path)
+        }
+    }
+
+    /// Formats a page title with the site name appended.
+    ///
+    /// # Arguments
+    /// * `title` - The page title; `None` yields just the site name
+    ///
+    /// # Returns
+    /// * `String` - The full title, e.g. "Reservoir · Sector F Labs"
+    pub fn page_title(&self, title: Option<&str>) -> String {
+        match title {
+            Some(title) if title != self.name => format!("{} · {}", title, self.name),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Renders the navigation menu for a page, marking the page's own entry.
+    ///
+    /// # Arguments
+    /// * `current` - The site-relative path of the page, e.g. `/projects/reservoir/`
+    ///
+    /// # Returns
+    /// * `String` - The menu's `<li>` elements
+    pub fn menu_html(&self, current: &str) -> String {
+        templates::create_menu_html(&self.menu, current)
+    }
+}
+
+/// Today's date in UTC, `YYYY-MM-DD`.
+fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default();
+    let mut date = Timestamp::new(seconds, 0).to_rfc3339();
+    date.truncate(10);
+    date
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absolute_url() {
+        let site = Site::new("Sector F Labs", "https://sectorflabs.com/", "");
+        assert_eq!(site.absolute_url("/"), "https://sectorflabs.com/");
+        assert_eq!(
+            site.absolute_url("projects/reservoir/logo.png"),
+            "https://sectorflabs.com/projects/reservoir/logo.png"
+        );
+    }
+
+    #[test]
+    fn test_page_title() {
+        let site = Site::new("Sector F Labs", "https://sectorflabs.com", "");
+        assert_eq!(site.page_title(None), "Sector F Labs");
+        assert_eq!(site.page_title(Some("Sector F Labs")), "Sector F Labs");
+        assert_eq!(
+            site.page_title(Some("Reservoir")),
+            "Reservoir · Sector F Labs"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Sitemap and robots.txt generation for search engines.
+
+use crate::html;
+
+/// A single page listed in the sitemap.
+#[derive(Debug, Clone)]
+pub struct SitemapEntry {
+    /// Absolute URL of the page
+    pub url: String,
+    /// Last modification date in W3C datetime format, e.g. RFC 3339
+    pub lastmod: Option<String>,
+}
+
+/// Creates the `sitemap.xml` document.
+///
+/// # Arguments
+/// * `entries` - The pages to list, in output order
+///
+/// # Returns
+/// * `String` - The sitemap XML
+pub fn create_sitemap(entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for entry in entries {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", html::escape(&entry.url)));
+        if let Some(lastmod) = &entry.lastmod {
+            xml.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                html::escape(lastmod)
+            ));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Creates a `robots.txt` that allows all crawlers and points at the sitemap.
+///
+/// # Arguments
+/// * `sitemap_url` - Absolute URL of `sitemap.xml`
+///
+/// # Returns
+/// * `String` - The robots.txt content
+pub fn create_robots_txt(sitemap_url: &str) -> String {
+    format!("User-agent: *\nAllow: /\n\nSitemap: {}\n", sitemap_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_sitemap() {
+        let entries = vec![
+            SitemapEntry {
+                url: "https://sectorflabs.com/".to_string(),
+                lastmod: None,
+            },
+            SitemapEntry {
+                url: "https://sectorflabs.com/projects/a&b/".to_string(),
+                lastmod: Some("2026-10-01".to_string()),
+            },
+        ];
+        let xml = create_sitemap(&entries);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("<url>\n    <loc>https://sectorflabs.com/</loc>\n  </url>"));
+        assert!(xml.contains("<loc>https://sectorflabs.com/projects/a&amp;b/</loc>\n    <lastmod>2026-10-01</lastmod>"));
+        assert!(xml.ends_with("</urlset>\n"));
+    }
+
+    #[test]
+    fn test_create_robots_txt() {
+        let robots = create_robots_txt("https://sectorflabs.com/sitemap.xml");
+        assert_eq!(
+            robots,
+            "User-agent: *\nAllow: /\n\nSitemap: https://sectorflabs.com/sitemap.xml\n"
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! Social card rendering for OpenGraph previews.
+//!
+//! Each page gets a 1200x630 PNG showing the site logo, the page title and a
+//! tagline, drawn in the site's brand colours with the bundled UbuntuMono fonts.
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont, point};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use tiny_skia::{
+    Color, FilterQuality, Paint, Pixmap, PixmapPaint, PremultipliedColorU8, Rect, Transform,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Filename used for generated social cards inside each page's output directory.
+pub const CARD_FILENAME: &str = "social-card.png";
+
+/// An opaque RGB colour.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Parses a `#rgb` or `#rrggbb` hex colour.
+    ///
+    /// # Arguments
+    /// * `hex` - The colour string
+    ///
+    /// # Returns
+    /// * `Option<Rgb>` - The colour, or None if the string is not a hex colour
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.trim().strip_prefix('#')?;
+        // Only ASCII hex digits, so byte slicing below stays on char boundaries
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        match digits.len() {
+            3 => {
+                let expand = |i: usize| channel(&digits[i..i + 1].repeat(2));
+                Some(Self(expand(0)?, expand(1)?, expand(2)?))
+            }
+            6 => Some(Self(
+                channel(&digits[0..2])?,
+                channel(&digits[2..4])?,
+                channel(&digits[4..6])?,
+            )),
+            _ => None,
+        }
+    }
+
+    fn to_color(self) -> Color {
+        Color::from_rgba8(self.0, self.1, self.2, 255)
+    }
+}
+
+/// Brand colours used on social cards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Brand {
+    /// Card background
+    pub background: Rgb,
+    /// Title and site name
+    pub text: Rgb,
+    /// Tagline
+    pub muted: Rgb,
+    /// Decorative bars
+    pub accent: Rgb,
+}
+
+impl Default for Brand {
+    fn default() -> Self {
+        Self {
+            background: Rgb(0xff, 0xff, 0xff),
+            text: Rgb(0x0f, 0x0c, 0x0a),
+            muted: Rgb(0x2f, 0x29, 0x25),
+            accent: Rgb(0xe0, 0x5a, 0x00),
+        }
+    }
+}
+
+impl Brand {
+    /// Reads brand colours from the light-mode `:root` block of a stylesheet.
+    ///
+    /// Uses `--bg-color`, `--title-color`, `--subtitle-color` and `--accent-color`;
+    /// any variable that is missing keeps its default.
+    ///
+    /// # Arguments
+    /// * `css` - The stylesheet source
+    ///
+    /// # Returns
+    /// * `Brand` - The brand colours
+    pub fn from_css(css: &str) -> Self {
+        let mut brand = Self::default();
+        let Some(block) = css
+            .find(":root")
+            .and_then(|start| css[start..].split_once('{'))
+            .and_then(|(_, rest)| rest.split_once('}'))
+            .map(|(block, _)| block)
+        else {
+            return brand;
+        };
+
+        for declaration in block.split(';') {
+            let Some((name, value)) = declaration.split_once(':') else {
+                continue;
+            };
+            let Some(colour) = Rgb::from_hex(value) else {
+                continue;
+            };
+            // Strip any comment preceding the variable name
+            match name.rsplit("*/").next().unwrap_or(name).trim() {
+                "--bg-color" => brand.background = colour,
+                "--title-color" => brand.text = colour,
+                "--subtitle-color" => brand.muted = colour,
+                "--accent-color" => brand.accent = colour,
+                _ => {}
+            }
+        }
+        brand
+    }
+}
+
+/// Geometry of a social card, in pixels.
+#[derive(Debug, Clone)]
+pub struct CardTemplate {
+    pub width: u32,
+    pub height: u32,
+    /// Outer margin around all content
+    pub padding: f32,
+    /// Height of the accent bars at the top and bottom edges
+    pub bar_height: f32,
+    /// Edge length of the square logo
+    pub logo_size: f32,
+    pub site_name_size: f32,
+    pub title_size: f32,
+    pub title_max_lines: usize,
+    pub tagline_size: f32,
+    pub tagline_max_lines: usize,
+}
+
+impl Default for CardTemplate {
+    fn default() -> Self {
+        Self {
+            width: 1200,
+            height: 630,
+            padding: 80.0,
+            bar_height: 16.0,
+            logo_size: 120.0,
+            site_name_size: 44.0,
+            title_size: 80.0,
+            title_max_lines: 2,
+            tagline_size: 36.0,
+            tagline_max_lines: 3,
+        }
+    }
+}
+
+/// Renders social card images with preloaded fonts, logo and colours.
+pub struct CardRenderer {
+    regular: FontVec,
+    bold: FontVec,
+    logo: Option<Pixmap>,
+    brand: Brand,
+    template: CardTemplate,
+}
+
+impl CardRenderer {
+    /// Loads fonts, logo and brand colours from the site's static assets.
+    ///
+    /// Expects `fonts/UbuntuMono-Regular.ttf`, `fonts/UbuntuMono-Bold.ttf` and
+    /// `style.css` under `assets_dir`. `logo-transparent.png` is optional.
+    ///
+    /// # Arguments
+    /// * `assets_dir` - The site output directory holding the static assets, e.g. `docs`
+    ///
+    /// # Returns
+    /// * `Result<CardRenderer>` - The renderer, or an error if fonts cannot be loaded
+    pub fn load(assets_dir: &str) -> Result<Self> {
+        let dir = Path::new(assets_dir);
+        let load_font = |name: &str| -> Result<FontVec> {
+            let path = dir.join("fonts").join(name);
+            let data = fs::read(&path)
+                .map_err(|e| format!("Failed to read font {}: {}", path.display(), e))?;
+            Ok(FontVec::try_from_vec(data)
+                .map_err(|e| format!("Invalid font {}: {}", path.display(), e))?)
+        };
+
+        let css = fs::read_to_string(dir.join("style.css")).unwrap_or_default();
+        let logo = fs::read(dir.join("logo-transparent.png"))
+            .ok()
+            .and_then(|data| Pixmap::decode_png(&data).ok());
+
+        Ok(Self {
+            regular: load_font("UbuntuMono-Regular.ttf")?,
+            bold: load_font("UbuntuMono-Bold.ttf")?,
+            logo,
+            brand: Brand::from_css(&css),
+            template: CardTemplate::default(),
+        })
+    }
+
+    /// Renders a card and writes it as `social-card.png` into `output_dir`.
+    ///
+    /// # Arguments
+    /// * `output_dir` - The page's output directory
+    /// * `site_name` - The site name shown next to the logo
+    /// * `title` - The page title
+    /// * `tagline` - Optional tagline, e.g. the README's first paragraph
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error result
+    pub fn write(
+        &self,
+        output_dir: &str,
+        site_name: &str,
+        title: &str,
+        tagline: Option<&str>,
+    ) -> Result<()> {
+        let png = self.render(site_name, title, tagline)?;
+        let path = Path::new(output_dir).join(CARD_FILENAME);
+        fs::write(&path, png)
+            .map_err(|e| format!("Failed to write social card {}: {}", path.display(), e))?;
+        Ok(())
+    }
+
+    /// Renders a card to PNG bytes.
+    ///
+    /// # Arguments
+    /// * `site_name` - The site name shown next to the logo
+    /// * `title` - The page title
+    /// * `tagline` - Optional tagline
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - The encoded PNG
+    pub fn render(&self, site_name: &str, title: &str, tagline: Option<&str>) -> Result<Vec<u8>> {
+        let t = &self.template;
+        let mut canvas =
+            Pixmap::new(t.width, t.height).ok_or("Social card dimensions must be non-zero")?;
+        canvas.fill(self.brand.background.to_color());
+
+        // Accent bars along the top and bottom edges
+        let mut paint = Paint::default();
+        paint.set_color(self.brand.accent.to_color());
+        let width = t.width as f32;
+        let height = t.height as f32;
+        for y in [0.0, height - t.bar_height] {
+            if let Some(rect) = Rect::from_xywh(0.0, y, width, t.bar_height) {
+                canvas.fill_rect(rect, &paint, Transform::identity(), None);
+            }
+        }
+
+        // Logo and site name
+        let mut text_x = t.padding;
+        if let Some(logo) = &self.logo {
+            let scale = t.logo_size / logo.width().max(logo.height()) as f32;
+            canvas.draw_pixmap(
+                0,
+                0,
+                logo.as_ref(),
+                &PixmapPaint {
+                    quality: FilterQuality::Bicubic,
+                    ..PixmapPaint::default()
+                },
+                Transform::from_row(scale, 0.0, 0.0, scale, t.padding, t.padding),
+                None,
+            );
+            text_x += t.logo_size + 32.0;
+        }
+        let header_baseline = t.padding + (t.logo_size + t.site_name_size * 0.7) / 2.0;
+        self.draw_text(
+            &mut canvas,
+            &self.bold,
+            t.site_name_size,
+            self.brand.text,
+            text_x,
+            header_baseline,
+            site_name,
+        );
+
+        // Title and tagline, wrapped to the content width
+        let content_width = width - 2.0 * t.padding;
+        let mut baseline = t.padding + t.logo_size + 48.0 + t.title_size;
+        for line in wrap(
+            &self.bold,
+            t.title_size,
+            title,
+            content_width,
+            t.title_max_lines,
+        ) {
+            self.draw_text(
+                &mut canvas,
+                &self.bold,
+                t.title_size,
+                self.brand.text,
+                t.padding,
+                baseline,
+                &line,
+            );
+            baseline += t.title_size * 1.1;
+        }
+
+        if let Some(tagline) = tagline {
+            baseline += t.tagline_size * 0.4;
+            let lines = wrap(
+                &self.regular,
+                t.tagline_size,
+                tagline,
+                content_width,
+                t.tagline_max_lines,
+            );
+            for line in lines {
+                if baseline > height - t.bar_height - t.tagline_size * 0.5 {
+                    break;
+                }
+                self.draw_text(
+                    &mut canvas,
+                    &self.regular,
+                    t.tagline_size,
+                    self.brand.muted,
+                    t.padding,
+                    baseline,
+                    &line,
+                );
+                baseline += t.tagline_size * 1.3;
+            }
+        }
+
+        Ok(canvas.encode_png()?)
+    }
+
+    /// Draws a single line of text with its baseline at `y`.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text(
+        &self,
+        canvas: &mut Pixmap,
+        font: &FontVec,
+        size: f32,
+        colour: Rgb,
+        x: f32,
+        y: f32,
+        text: &str,
+    ) {
+        let scaled = font.as_scaled(PxScale::from(size));
+        let width = canvas.width() as i32;
+        let height = canvas.height() as i32;
+        let pixels = canvas.pixels_mut();
+
+        let mut caret = x;
+        for c in text.chars() {
+            let glyph = scaled.scaled_glyph(c);
+            let advance = scaled.h_advance(glyph.id);
+            let glyph = glyph.id.with_scale_and_position(size, point(caret, y));
+            caret += advance;
+
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i32 + gx as i32;
+                let py = bounds.min.y as i32 + gy as i32;
+                if px < 0 || py < 0 || px >= width || py >= height {
+                    return;
+                }
+                let pixel = &mut pixels[(py * width + px) as usize];
+                let blend = |dst: u8, src: u8| {
+                    (dst as f32 + (src as f32 - dst as f32) * coverage.min(1.0)).round() as u8
+                };
+                if let Some(blended) = PremultipliedColorU8::from_rgba(
+                    blend(pixel.red(), colour.0),
+                    blend(pixel.green(), colour.1),
+                    blend(pixel.blue(), colour.2),
+                    255,
+                ) {
+                    *pixel = blended;
+                }
+            });
+        }
+    }
+}
+
+/// Word-wraps text to fit `max_width`, truncating with an ellipsis after `max_lines`.
+fn wrap(font: &FontVec, size: f32, text: &str, max_width: f32, max_lines: usize) -> Vec<String> {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let measure = |s: &str| -> f32 {
+        s.chars()
+            .map(|c| scaled.h_advance(scaled.glyph_id(c)))
+            .sum()
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut truncated = false;
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if measure(&candidate) <= max_width || current.is_empty() {
+            current = candidate;
+            continue;
+        }
+        lines.push(std::mem::replace(&mut current, word.to_string()));
+        if lines.len() == max_lines {
+            truncated = true;
+            break;
+        }
+    }
+    if !truncated && !current.is_empty() {
+        lines.push(current);
+    }
+
+    if truncated && let Some(last) = lines.last_mut() {
+        while !last.is_empty() && measure(&format!("{}…", last)) > max_width {
+            last.pop();
+        }
+        last.push('…');
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_from_hex() {
+        assert_eq!(Rgb::from_hex("#e05a00"), Some(Rgb(0xe0, 0x5a, 0x00)));
+        assert_eq!(Rgb::from_hex(" #fff"), Some(Rgb(0xff, 0xff, 0xff)));
+        assert_eq!(Rgb::from_hex("var(--x)"), None);
+        assert_eq!(Rgb::from_hex("#aébcd"), None);
+        assert_eq!(Rgb::from_hex("#+f+f+f"), None);
+        assert_eq!(Rgb::from_hex("#+ff"), None);
+    }
+
+    #[test]
+    fn test_brand_from_css() {
+        let css = ":root {\n    /* Light */\n    --accent-color: #123456;\n    --bg-color: #000;\n}\n@media (prefers-color-scheme: dark) { :root { --bg-color: #fff; } }";
+        let brand = Brand::from_css(css);
+        assert_eq!(brand.accent, Rgb(0x12, 0x34, 0x56));
+        assert_eq!(brand.background, Rgb(0, 0, 0));
+        assert_eq!(brand.text, Brand::default().text);
+    }
+
+    #[test]
+    fn test_render_card() {
+        let renderer = CardRenderer::load("docs").unwrap();
+        let png = renderer
+            .render("Sector F Labs", "Reservoir", Some("Memory for LLM chats"))
+            .unwrap();
+        let decoded = Pixmap::decode_png(&png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (1200, 630));
+    }
+
+    #[test]
+    fn test_wrap_truncates() {
+        let renderer = CardRenderer::load("docs").unwrap();
+        let text = "one two three four five six seven eight nine ten";
+        let lines = wrap(&renderer.regular, 36.0, text, 200.0, 2);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with('…'));
+    }
+}
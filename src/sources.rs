@@ -0,0 +1,411 @@
+//! Project sources fetched from git remotes.
+//!
+//! A project can point at a repository URL and a branch, tag or commit instead
+//! of a sibling checkout. The repository is cloned into `SOURCES_DIR` and the
+//! commit each revision resolved to is recorded in `LOCKFILE`, so later builds
+//! check out exactly the same source until the revision changes or a build
+//! runs with `--update`.
+
+use crate::git;
+use crate::pool;
+use crate::project::Project;
+use crate::url::percent_decode;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Lockfile recording the commit each git source is pinned to.
+pub const LOCKFILE: &str = "sources.lock";
+
+/// Directory git sources are cloned into.
+pub const SOURCES_DIR: &str = ".site-cache/sources";
+
+/// A repository URL plus the revision to build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitSource {
+    /// Repository URL or local path, e.g. `https://github.com/Sector-F-Labs/reservoir`
+    pub url: String,
+    /// Branch, tag or commit hash
+    pub rev: String,
+}
+
+/// Pinned commits keyed by repository URL and revision.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Lockfile {
+    entries: BTreeMap<(String, String), String>,
+}
+
+impl Lockfile {
+    /// Loads a lockfile, returning an empty one if it doesn't exist.
+    ///
+    /// Each line reads `<url> <rev> <commit>`; blank lines and `#` comments are ignored.
+    /// Whitespace and `%` in the URL and revision are percent-encoded.
+    ///
+    /// # Arguments
+    /// * `path` - The lockfile path, usually `LOCKFILE`
+    ///
+    /// # Returns
+    /// * `Result<Lockfile>` - The entries, or an error if a line is malformed
+    pub fn load(path: &str) -> Result<Self> {
+        let Ok(text) = fs::read_to_string(path) else {
+            return Ok(Self::default());
+        };
+        let mut lockfile = Self::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let malformed = || format!("{}:{}: expected `<url> <rev> <commit>`", path, number + 1);
+            let [url, rev, commit] = fields[..] else {
+                return Err(malformed().into());
+            };
+            let url = percent_decode(url).ok_or_else(malformed)?;
+            let rev = percent_decode(rev).ok_or_else(malformed)?;
+            lockfile.entries.insert((url, rev), commit.to_string());
+        }
+        Ok(lockfile)
+    }
+
+    /// Writes the lockfile.
+    pub fn save(&self, path: &str) -> Result<()> {
+        let mut text = String::from(
+            "# Commits git project sources are pinned to. Run `site build --update` to move them forward.\n",
+        );
+        for ((url, rev), commit) in &self.entries {
+            text.push_str(&format!(
+                "{} {} {}\n",
+                escape_field(url),
+                escape_field(rev),
+                commit
+            ));
+        }
+        fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        Ok(())
+    }
+
+    /// Gets the commit a source is pinned to.
+    pub fn get(&self, source: &GitSource) -> Option<&str> {
+        self.entries
+            .get(&(source.url.clone(), source.rev.clone()))
+            .map(String::as_str)
+    }
+
+    /// Pins a source to a commit.
+    pub fn insert(&mut self, source: &GitSource, commit: String) {
+        self.entries
+            .insert((source.url.clone(), source.rev.clone()), commit);
+    }
+
+    /// Drops entries for sources that are no longer configured.
+    pub fn retain(&mut self, sources: &[&GitSource]) {
+        self.entries.retain(|(url, rev), _| {
+            sources
+                .iter()
+                .any(|source| &source.url == url && &source.rev == rev)
+        });
+    }
+}
+
+/// Percent-encodes whitespace and `%` so a lockfile field never splits.
+fn escape_field(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '%' || c.is_whitespace() {
+            let mut buffer = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                escaped.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Clones or updates a git source and checks out its pinned commit.
+///
+/// An existing clone is used as is, so builds work offline once a source has
+/// been cloned. The network is only touched when the clone is missing or was
+/// made from a different URL, when the revision isn't in it yet, or when
+/// `update` asks for the latest commit of an unlocked revision.
+///
+/// # Arguments
+/// * `source` - The repository and revision
+/// * `dir` - The local clone directory
+/// * `locked` - The commit recorded in the lockfile, if any
+/// * `update` - Whether to fetch before resolving an unlocked revision
+///
+/// # Returns
+/// * `Result<String>` - The commit hash that was checked out
+pub fn checkout_source(
+    source: &GitSource,
+    dir: &Path,
+    locked: Option<&str>,
+    update: bool,
+) -> Result<String> {
+    let mut fresh = !git::is_git_repository(&dir.to_string_lossy());
+    if !fresh && git::origin_url(dir).as_deref() != Some(source.url.as_str()) {
+        // The source moved to another repository; don't build from the old clone
+        fs::remove_dir_all(dir)
+            .map_err(|e| format!("Failed to remove {}: {}", dir.display(), e))?;
+        fresh = true;
+    }
+    if fresh {
+        git::clone_repository(&source.url, dir)?;
+    }
+
+    let (rev, missing) = match locked {
+        Some(commit) => (
+            commit,
+            format!(
+                "Locked commit {} for {} ({}) not found",
+                commit, source.url, source.rev
+            ),
+        ),
+        None => (
+            source.rev.as_str(),
+            format!("Revision {} not found in {}", source.rev, source.url),
+        ),
+    };
+    let stale = update && locked.is_none();
+    if !fresh && (stale || git::resolve_revision(dir, rev).is_none()) {
+        git::fetch(dir)?;
+    }
+    let commit = git::resolve_revision(dir, rev).ok_or(missing)?;
+    git::checkout(dir, &commit)?;
+    Ok(commit)
+}
+
+/// How `fetch_sources` treats the lockfile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Check out pinned commits and pin any new source (the default)
+    Resolve,
+    /// Fail if a source isn't pinned, for reproducible CI builds (`--locked`)
+    Locked,
+    /// Ignore the lockfile and move every source to its latest commit (`--update`)
+    Update,
+}
+
+/// Checks out every project that has a git source and updates the lockfile.
+///
+/// # Arguments
+/// * `projects` - All configured projects; those without a git source are skipped
+/// * `lockfile` - The lockfile, updated with newly resolved revisions
+/// * `jobs` - Number of repositories fetched in parallel
+/// * `mode` - Whether unpinned sources are resolved, rejected or everything is updated
+pub fn fetch_sources(
+    projects: &[Project],
+    lockfile: &mut Lockfile,
+    jobs: usize,
+    mode: LockMode,
+) -> Result<()> {
+    if mode == LockMode::Locked
+        && let Some(project) = projects.iter().find(|project| {
+            project
+                .git_source
+                .as_ref()
+                .is_some_and(|source| lockfile.get(source).is_none())
+        })
+    {
+        return Err(format!(
+            "{} isn't pinned in {}; run a build without --locked and commit {}",
+            project.name(),
+            LOCKFILE,
+            LOCKFILE
+        )
+        .into());
+    }
+
+    let update = mode == LockMode::Update;
+    let results = pool::map(jobs, projects, |project| {
+        let source = project.git_source.as_ref()?;
+        let locked = lockfile.get(source).filter(|_| !update);
+        Some(
+            checkout_source(source, Path::new(&project.source_dir), locked, update)
+                .map_err(|e| e.to_string()),
+        )
+    });
+
+    let mut sources = Vec::new();
+    for (project, result) in projects.iter().zip(results) {
+        let (Some(source), Some(result)) = (&project.git_source, result) else {
+            continue;
+        };
+        let commit = result.map_err(|e| format!("Failed to fetch {}: {}", project.name(), e))?;
+        println!(
+            "📥 Checked out {} at {} ({})",
+            project.name(),
+            source.rev,
+            &commit[..7.min(commit.len())]
+        );
+        lockfile.insert(source, commit);
+        sources.push(source);
+    }
+    lockfile.retain(&sources);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{TempDir, commit};
+    use git2::Repository;
+
+    /// Commits a README on top of `main` in a bare repository.
+    fn commit_readme(repo: &Repository, content: &str) -> String {
+        commit(
+            repo,
+            "Test test@example.com",
+            1_000_000,
+            &[("README.md", content)],
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn test_lockfile_roundtrip() {
+        let dir = TempDir::new("lock");
+        let path = dir.join("sources.lock");
+        let path = path.to_str().unwrap();
+        let source = GitSource {
+            url: "https://example.com/repo.git".to_string(),
+            rev: "v1.0".to_string(),
+        };
+        // Local paths may contain spaces and percent signs
+        let local = GitSource {
+            url: "/tmp/my repos/100% done.git".to_string(),
+            rev: "main".to_string(),
+        };
+        let mut lockfile = Lockfile::default();
+        lockfile.insert(&source, "abc123".to_string());
+        lockfile.insert(&local, "def456".to_string());
+        lockfile.save(path).unwrap();
+        assert!(
+            fs::read_to_string(path)
+                .unwrap()
+                .contains("/tmp/my%20repos/100%25%20done.git main def456\n")
+        );
+
+        let loaded = Lockfile::load(path).unwrap();
+        assert_eq!(loaded.get(&source), Some("abc123"));
+        assert_eq!(loaded.get(&local), Some("def456"));
+        assert_eq!(loaded, lockfile);
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(Lockfile::load(path).unwrap(), Lockfile::default());
+    }
+
+    #[test]
+    fn test_checkout_source_from_bare_repo() {
+        let root = TempDir::new("sources");
+        let remote = Repository::init_bare(root.join("remote.git")).unwrap();
+        remote.set_head("refs/heads/main").unwrap();
+        let first = commit_readme(&remote, "# One\n");
+        let head = remote.head().unwrap().peel_to_commit().unwrap();
+        remote
+            .tag_lightweight("v1", head.as_object(), false)
+            .unwrap();
+
+        let source = GitSource {
+            url: root.join("remote.git").to_string_lossy().into_owned(),
+            rev: "main".to_string(),
+        };
+        let clone = root.join("cache/project");
+        assert_eq!(
+            checkout_source(&source, &clone, None, false).unwrap(),
+            first
+        );
+        assert_eq!(
+            fs::read_to_string(clone.join("README.md")).unwrap(),
+            "# One\n"
+        );
+
+        // Push a new commit: locked and plain unlocked builds stay on the clone,
+        // only an update moves forward
+        let second = commit_readme(&remote, "# Two\n");
+        assert_eq!(
+            checkout_source(&source, &clone, Some(&first), true).unwrap(),
+            first
+        );
+        assert_eq!(
+            checkout_source(&source, &clone, None, false).unwrap(),
+            first
+        );
+        assert_eq!(
+            checkout_source(&source, &clone, None, true).unwrap(),
+            second
+        );
+        assert_eq!(
+            fs::read_to_string(clone.join("README.md")).unwrap(),
+            "# Two\n"
+        );
+
+        // A locked commit missing from the clone is fetched
+        let third = commit_readme(&remote, "# Three\n");
+        assert_eq!(
+            checkout_source(&source, &clone, Some(&third), false).unwrap(),
+            third
+        );
+
+        let tag = GitSource {
+            rev: "v1".to_string(),
+            ..source.clone()
+        };
+        assert_eq!(checkout_source(&tag, &clone, None, false).unwrap(), first);
+        let missing = GitSource {
+            rev: "nope".to_string(),
+            ..source.clone()
+        };
+        assert!(checkout_source(&missing, &clone, None, false).is_err());
+
+        // Pointing the source at another repository replaces the clone
+        let other = Repository::init_bare(root.join("other.git")).unwrap();
+        other.set_head("refs/heads/main").unwrap();
+        let moved = commit_readme(&other, "# Other\n");
+        let moved_source = GitSource {
+            url: root.join("other.git").to_string_lossy().into_owned(),
+            ..source
+        };
+        assert_eq!(
+            checkout_source(&moved_source, &clone, None, false).unwrap(),
+            moved
+        );
+        assert_eq!(
+            git::origin_url(&clone).as_deref(),
+            Some(moved_source.url.as_str())
+        );
+    }
+
+    #[test]
+    fn test_fetch_sources_locked() {
+        let root = TempDir::new("locked");
+        let remote = Repository::init_bare(root.join("remote.git")).unwrap();
+        remote.set_head("refs/heads/main").unwrap();
+        let first = commit_readme(&remote, "# One\n");
+        let mut project = Project::from_git(
+            root.join("remote.git").to_string_lossy().into_owned(),
+            "main".to_string(),
+            "docs/projects/locked".to_string(),
+        );
+        project.source_dir = root.join("clone").to_string_lossy().into_owned();
+        let projects = [project];
+
+        let mut lockfile = Lockfile::default();
+        assert!(fetch_sources(&projects, &mut lockfile, 1, LockMode::Locked).is_err());
+        fetch_sources(&projects, &mut lockfile, 1, LockMode::Resolve).unwrap();
+        let source = projects[0].git_source.as_ref().unwrap();
+        assert_eq!(lockfile.get(source), Some(first.as_str()));
+
+        let second = commit_readme(&remote, "# Two\n");
+        fetch_sources(&projects, &mut lockfile, 1, LockMode::Locked).unwrap();
+        assert_eq!(lockfile.get(source), Some(first.as_str()));
+        fetch_sources(&projects, &mut lockfile, 1, LockMode::Update).unwrap();
+        assert_eq!(lockfile.get(source), Some(second.as_str()));
+    }
+}
@@ -0,0 +1,163 @@
+//! Schema.org structured data (JSON-LD) for the page `<head>`.
+//!
+//! Every page describes the site's `Organization`. Project pages add a
+//! `SoftwareSourceCode` entry, and experiments, whose output directory starts
+//! with `exp-`, an `Article` with their publication dates. Entries share one
+//! `@graph` and point at the organization by `@id`.
+
+use crate::project::Project;
+use crate::search::json_string;
+use crate::site::Site;
+
+/// Builds a JSON object from already-encoded values, skipping missing ones.
+fn json_object(fields: &[(&str, Option<String>)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .filter_map(|(name, value)| Some(format!("{}:{}", json_string(name), value.as_ref()?)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Gets the `@id` of the site's organization entry.
+fn organization_id(site: &Site) -> String {
+    site.absolute_url("/#organization")
+}
+
+/// Creates the `Organization` entry for the site.
+fn organization(site: &Site) -> String {
+    json_object(&[
+        ("@type", Some(json_string("Organization"))),
+        ("@id", Some(json_string(&organization_id(site)))),
+        ("name", Some(json_string(&site.name))),
+        ("url", Some(json_string(&site.absolute_url("/")))),
+        ("logo", Some(json_string(&site.absolute_url(&site.image)))),
+        ("description", Some(json_string(&site.description))),
+    ])
+}
+
+/// Wraps entries in a JSON-LD `<script>` element.
+fn script(entries: &[String]) -> String {
+    format!(
+        "<script type=\"application/ld+json\">{{\"@context\":\"https://schema.org\",\"@graph\":[{}]}}</script>",
+        entries.join(",")
+    )
+}
+
+/// Creates the structured data for a page that only describes the site.
+///
+/// # Arguments
+/// * `site` - The site settings
+///
+/// # Returns
+/// * `String` - A JSON-LD `<script>` element with the `Organization`
+pub fn create_site_json_ld(site: &Site) -> String {
+    script(&[organization(site)])
+}
+
+/// Creates the structured data for a project page.
+///
+/// # Arguments
+/// * `project` - The project
+/// * `site` - The site settings
+/// * `description` - The page description
+/// * `image_url` - Absolute URL of the page's social image
+///
+/// # Returns
+/// * `String` - A JSON-LD `<script>` element with the `Organization`, the
+///   project's `SoftwareSourceCode` and, for experiments, an `Article`
+pub fn create_project_json_ld(
+    project: &Project,
+    site: &Site,
+    description: &str,
+    image_url: &str,
+) -> String {
+    let url = site.absolute_url(&project.url());
+    let author = Some(format!(
+        "{{\"@id\":{}}}",
+        json_string(&organization_id(site))
+    ));
+    let text = |value: Option<&str>| value.map(json_string);
+
+    let mut entries = vec![
+        organization(site),
+        json_object(&[
+            ("@type", Some(json_string("SoftwareSourceCode"))),
+            ("@id", Some(json_string(&format!("{}#software", url)))),
+            ("name", Some(json_string(project.display_title()))),
+            ("description", Some(json_string(description))),
+            ("url", Some(json_string(&url))),
+            ("image", Some(json_string(image_url))),
+            (
+                "codeRepository",
+                text(
+                    project
+                        .remote()
+                        .as_ref()
+                        .map(|remote| remote.web_url.as_str()),
+                ),
+            ),
+            ("programmingLanguage", text(project.language.as_deref())),
+            ("license", text(project.license.as_deref())),
+            ("version", text(project.version.as_deref())),
+            (
+                "keywords",
+                (!project.tags.is_empty()).then(|| json_string(&project.tags.join(", "))),
+            ),
+            ("author", author.clone()),
+        ]),
+    ];
+    if project.is_experiment() {
+        entries.push(json_object(&[
+            ("@type", Some(json_string("Article"))),
+            ("headline", Some(json_string(project.display_title()))),
+            ("description", Some(json_string(description))),
+            ("url", Some(json_string(&url))),
+            ("mainEntityOfPage", Some(json_string(&url))),
+            ("image", Some(json_string(image_url))),
+            ("datePublished", text(project.published.as_deref())),
+            ("dateModified", text(project.updated.as_deref())),
+            ("author", author.clone()),
+            ("publisher", author),
+        ]));
+    }
+    script(&entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_project_json_ld() {
+        let site = Site::new("Sector F Labs", "https://sectorflabs.com", "Labs");
+        assert_eq!(
+            create_site_json_ld(&site),
+            "<script type=\"application/ld+json\">{\"@context\":\"https://schema.org\",\"@graph\":[{\"@type\":\"Organization\",\"@id\":\"https://sectorflabs.com/#organization\",\"name\":\"Sector F Labs\",\"url\":\"https://sectorflabs.com/\",\"logo\":\"https://sectorflabs.com/logo.png\",\"description\":\"Labs\"}]}</script>"
+        );
+
+        let mut project = Project::new(
+            "../reservoir".to_string(),
+            "docs/projects/reservoir".to_string(),
+        );
+        project.title = Some("Reservoir".to_string());
+        project.github_url = Some("git@github.com:Sector-F-Labs/reservoir.git".to_string());
+        project.language = Some("Rust".to_string());
+        project.license = Some("MIT".to_string());
+        project.version = Some("v0.4.0".to_string());
+        let json_ld =
+            create_project_json_ld(&project, &site, "Memory", "https://sectorflabs.com/a.png");
+        assert!(json_ld.contains("{\"@type\":\"SoftwareSourceCode\",\"@id\":\"https://sectorflabs.com/projects/reservoir/#software\",\"name\":\"Reservoir\",\"description\":\"Memory\",\"url\":\"https://sectorflabs.com/projects/reservoir/\",\"image\":\"https://sectorflabs.com/a.png\",\"codeRepository\":\"https://github.com/Sector-F-Labs/reservoir\",\"programmingLanguage\":\"Rust\",\"license\":\"MIT\",\"version\":\"v0.4.0\",\"author\":{\"@id\":\"https://sectorflabs.com/#organization\"}}"));
+        assert!(!json_ld.contains("Article"));
+
+        let mut experiment = Project::new(
+            "../exp-013-service-pipe".to_string(),
+            "docs/projects/exp-013-service-pipe".to_string(),
+        );
+        experiment.published = Some("2026-01-02T03:04:05+00:00".to_string());
+        let json_ld =
+            create_project_json_ld(&experiment, &site, "Pipes", "https://sectorflabs.com/a.png");
+        assert!(json_ld.contains("\"@type\":\"Article\",\"headline\":\"exp-013-service-pipe\""));
+        assert!(json_ld.contains("\"datePublished\":\"2026-01-02T03:04:05+00:00\""));
+        assert!(!json_ld.contains("dateModified"));
+    }
+}
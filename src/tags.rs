@@ -0,0 +1,248 @@
+//! Topic tags and their listing pages.
+//!
+//! Projects are tagged in code (`with_tags`) or with `tags` in front matter.
+//! The build writes `/tags/` listing every tag and `/tags/<tag>/` listing the
+//! projects with that tag, and each project page links to its tags.
+
+use crate::html;
+use crate::navigation;
+use crate::project::{self, Project};
+use crate::site::Site;
+use crate::social;
+use crate::structured_data;
+use crate::templates::{self, PageMeta};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Site-relative URL of the tag index.
+pub const TAGS_PATH: &str = "/tags/";
+
+/// A generated tag listing page.
+#[derive(Debug, Clone)]
+pub struct TagPage {
+    /// Page title, e.g. `Tagged "llm"`
+    pub title: String,
+    /// Site-relative URL, e.g. `/tags/llm/`
+    pub url: String,
+    /// The tag, or empty for the tag index
+    pub tag: String,
+    /// Page body HTML
+    pub content: String,
+}
+
+/// Gets the URL of a tag's listing page.
+///
+/// # Arguments
+/// * `tag` - The tag as written, e.g. `LLM`
+///
+/// # Returns
+/// * `String` - The site-relative URL, e.g. `/tags/llm/`
+pub fn tag_url(tag: &str) -> String {
+    format!("{}{}/", TAGS_PATH, html::slugify(tag.trim()))
+}
+
+/// Groups projects by tag slug, keeping the first spelling of each tag.
+fn group_by_tag(projects: &[Project]) -> BTreeMap<String, (String, Vec<&Project>)> {
+    let mut groups: BTreeMap<String, (String, Vec<&Project>)> = BTreeMap::new();
+    for project in project::sort_for_listing(projects) {
+        for tag in &project.tags {
+            let slug = html::slugify(tag.trim());
+            if slug.is_empty() {
+                continue;
+            }
+            let (_, tagged) = groups
+                .entry(slug)
+                .or_insert_with(|| (tag.trim().to_string(), Vec::new()));
+            if !tagged.iter().any(|p| p.output_dir == project.output_dir) {
+                tagged.push(project);
+            }
+        }
+    }
+    groups
+}
+
+/// Creates the tag links shown on a project page.
+///
+/// # Arguments
+/// * `tags` - The project's tags
+///
+/// # Returns
+/// * `String` - The tag list HTML, or an empty string if there are no tags
+pub fn create_tag_links(tags: &[String]) -> String {
+    let links: Vec<String> = tags
+        .iter()
+        .filter(|tag| !html::slugify(tag.trim()).is_empty())
+        .map(|tag| {
+            format!(
+                "<li><a href=\"{}\" rel=\"tag\">{}</a></li>",
+                html::escape(&tag_url(tag)),
+                html::escape(tag.trim())
+            )
+        })
+        .collect();
+    if links.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n<ul class=\"tag-list\" aria-label=\"Tags\">{}</ul>\n",
+        links.join("")
+    )
+}
+
+/// Creates the tag index and one listing page per tag.
+///
+/// # Arguments
+/// * `projects` - The processed projects
+///
+/// # Returns
+/// * `Vec<TagPage>` - The index followed by the tag pages in tag order, or
+///   nothing if no project is tagged
+pub fn create_tag_pages(projects: &[Project]) -> Vec<TagPage> {
+    let groups = group_by_tag(projects);
+    if groups.is_empty() {
+        return Vec::new();
+    }
+
+    let mut index = String::from("<h1>Tags</h1>\n<ul class=\"tag-index\">\n");
+    let mut pages = Vec::new();
+    for (tag, tagged) in groups.values() {
+        let url = tag_url(tag);
+        index.push_str(&format!(
+            "<li><a href=\"{}\" rel=\"tag\">{}</a> <span class=\"tag-count\">{}</span></li>\n",
+            html::escape(&url),
+            html::escape(tag),
+            tagged.len()
+        ));
+        let content = format!(
+            "<h1>Tagged “{}”</h1>\n<p><a href=\"{}\">All tags</a></p>\n<h2>Projects</h2>\n{}",
+            html::escape(tag),
+            TAGS_PATH,
+            html::create_project_grid(tagged)
+        );
+        pages.push(TagPage {
+            title: format!("Tagged “{}”", tag),
+            url,
+            tag: tag.clone(),
+            content: html::add_heading_ids(&content),
+        });
+    }
+    index.push_str("</ul>\n");
+
+    let mut all = vec![TagPage {
+        title: "Tags".to_string(),
+        url: TAGS_PATH.to_string(),
+        tag: String::new(),
+        content: html::add_heading_ids(&index),
+    }];
+    all.extend(pages);
+    all
+}
+
+/// Writes the tag pages, replacing any left from earlier builds.
+///
+/// # Arguments
+/// * `output_dir` - The site root, e.g. `docs`
+/// * `site` - The site settings
+/// * `layout` - The HTML layout template
+/// * `projects` - The processed projects
+///
+/// # Returns
+/// * `Result<Vec<TagPage>>` - The pages written, for the sitemap and search index
+pub fn write_tag_pages(
+    output_dir: &str,
+    site: &Site,
+    layout: &str,
+    projects: &[Project],
+) -> Result<Vec<TagPage>> {
+    let tags_dir = format!("{}{}", output_dir, TAGS_PATH);
+    if fs::metadata(&tags_dir).is_ok_and(|meta| meta.is_dir()) {
+        fs::remove_dir_all(&tags_dir)
+            .map_err(|e| format!("Failed to remove old tag pages {}: {}", tags_dir, e))?;
+    }
+
+    let pages = create_tag_pages(projects);
+    let titles: BTreeMap<String, String> = pages
+        .iter()
+        .map(|page| {
+            let name = if page.tag.is_empty() {
+                &page.title
+            } else {
+                &page.tag
+            };
+            (page.url.clone(), name.clone())
+        })
+        .collect();
+    for page in &pages {
+        let meta = PageMeta {
+            title: site.page_title(Some(&page.title)),
+            description: if page.tag.is_empty() {
+                format!("Projects on {} by topic", site.name)
+            } else {
+                format!("Projects on {} tagged {}", site.name, page.tag)
+            },
+            canonical_url: site.absolute_url(&page.url),
+            image_url: site.absolute_url(&format!("/{}", social::CARD_FILENAME)),
+            structured_data: structured_data::create_site_json_ld(site),
+        };
+        let page_layout = templates::apply_page_meta(layout, &meta);
+        let trail = navigation::breadcrumbs(&page.url, &titles);
+        let content = format!(
+            "{}{}{}",
+            navigation::create_breadcrumbs_html(&trail),
+            navigation::create_breadcrumbs_json_ld(site, &trail),
+            page.content
+        );
+        let html = templates::process_template(&page_layout, &content, &site.menu_html(&page.url));
+
+        let dir = format!("{}{}", output_dir, page.url);
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create output directory {}: {}", dir, e))?;
+        let output_file = format!("{}index.html", dir);
+        fs::write(&output_file, html)
+            .map_err(|e| format!("Failed to write output file {}: {}", output_file, e))?;
+    }
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, tags: &[&str]) -> Project {
+        let mut project = Project::new(format!("../{}", name), format!("docs/projects/{}", name));
+        project.title = Some(name.to_string());
+        project.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        project
+    }
+
+    #[test]
+    fn test_create_tag_links() {
+        assert_eq!(create_tag_links(&[]), "");
+        assert_eq!(
+            create_tag_links(&["LLM".to_string(), "C & C++".to_string()]),
+            "\n<ul class=\"tag-list\" aria-label=\"Tags\"><li><a href=\"/tags/llm/\" rel=\"tag\">LLM</a></li><li><a href=\"/tags/c--c/\" rel=\"tag\">C &amp; C++</a></li></ul>\n"
+        );
+    }
+
+    #[test]
+    fn test_create_tag_pages() {
+        assert!(create_tag_pages(&[project("a", &[])]).is_empty());
+
+        let projects = [
+            project("reservoir", &["llm", "rust"]),
+            project("md-chat", &["LLM"]),
+        ];
+        let pages = create_tag_pages(&projects);
+        let urls: Vec<&str> = pages.iter().map(|page| page.url.as_str()).collect();
+        assert_eq!(urls, vec!["/tags/", "/tags/llm/", "/tags/rust/"]);
+        assert!(pages[0].content.contains(
+            "<a href=\"/tags/llm/\" rel=\"tag\">LLM</a> <span class=\"tag-count\">2</span>"
+        ));
+        assert!(pages[1].content.contains("href=\"/projects/md-chat/\""));
+        assert!(pages[1].content.contains("href=\"/projects/reservoir/\""));
+        assert!(!pages[2].content.contains("md-chat"));
+    }
+}
@@ -1,7 +1,10 @@
-/// Template processing utilities for HTML template replacement.
-///
-/// This module provides functionality for processing HTML templates with placeholder
-/// replacement, commonly used in static site generation.
+//! Template processing utilities for HTML template replacement.
+//!
+//! This module provides functionality for processing HTML templates with placeholder
+//! replacement, commonly used in static site generation.
+
+use crate::html;
+use crate::project::SiteLink;
 
 /// Replaces template placeholders with actual content.
 ///
@@ -41,29 +44,70 @@ pub fn replace_template(html: &str, replacements: &[(&str, &str)]) -> String {
 
 /// Creates a navigation menu HTML from a list of site links.
 ///
-/// This function generates HTML list items for navigation menus from a collection
-/// of site links, commonly used in website headers or sidebars.
+/// Nested links become a nested `<ul>`. The link for the current page gets
+/// `aria-current="page"` and the `active` class, and entries leading to it get
+/// the `active-trail` class. Names and URLs are HTML-escaped, and external
+/// links open in a new tab with `rel="noopener"`.
 ///
 /// # Arguments
-/// * `links` - A slice of tuples containing (name, url) pairs for each link
+/// * `links` - The menu links
+/// * `current` - The site-relative path of the page being rendered, e.g. `/projects/reservoir/`
 ///
 /// # Returns
-/// * `String` - HTML string containing `<li><a>` elements for each link
+/// * `String` - HTML string containing `<li>` elements for each link
 ///
 /// # Examples
 /// ```
-/// let links = &[
-///     ("Home", "/"),
-///     ("About", "/about"),
-///     ("Contact", "/contact"),
+/// let links = [
+///     SiteLink::new("Home".to_string(), "/".to_string()),
+///     SiteLink::new("About".to_string(), "/about".to_string()),
 /// ];
-/// let menu = create_menu_html(links);
-/// // Returns: <li><a href="/">Home</a></li>\n<li><a href="/about">About</a></li>\n...
+/// let menu = create_menu_html(&links, "/about/");
+/// // Returns: <li><a href="/">Home</a></li>\n<li class="active"><a href="/about" aria-current="page">About</a></li>
 /// ```
-pub fn create_menu_html(links: &[(&str, &str)]) -> String {
+pub fn create_menu_html(links: &[SiteLink], current: &str) -> String {
     links
         .iter()
-        .map(|(name, url)| format!("<li><a href=\"{}\">{}</a></li>", url, name))
+        .map(|link| {
+            let class = if link.is_current(current) {
+                " class=\"active\""
+            } else if link.contains(current) {
+                " class=\"active-trail\""
+            } else {
+                ""
+            };
+            let name = html::escape(&link.name);
+            let label = if link.url.is_empty() {
+                format!("<span>{}</span>", name)
+            } else if link.is_external() {
+                format!(
+                    "<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>",
+                    html::escape(&link.url),
+                    name
+                )
+            } else {
+                let current = if link.is_current(current) {
+                    " aria-current=\"page\""
+                } else {
+                    ""
+                };
+                format!(
+                    "<a href=\"{}\"{}>{}</a>",
+                    html::escape(&link.url),
+                    current,
+                    name
+                )
+            };
+            let children = if link.children.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "\n<ul>\n{}\n</ul>\n",
+                    create_menu_html(&link.children, current)
+                )
+            };
+            format!("<li{}>{}{}</li>", class, label, children)
+        })
         .collect::<Vec<_>>()
         .join("\n")
 }
@@ -98,6 +142,46 @@ pub fn process_template(template: &str, main_content: &str, menu_items: &str) ->
     )
 }
 
+/// Per-page metadata rendered into the layout's `<head>`.
+#[derive(Debug, Clone)]
+pub struct PageMeta {
+    /// Full page title, including the site name
+    pub title: String,
+    /// Page description used for search results and social cards
+    pub description: String,
+    /// Absolute canonical URL of the page
+    pub canonical_url: String,
+    /// Absolute URL of the social card image
+    pub image_url: String,
+    /// JSON-LD `<script>` element describing the page, inserted as is
+    pub structured_data: String,
+}
+
+/// Fills the layout's `<head>` metadata placeholders for a single page.
+///
+/// Values are HTML-escaped, except the structured data, which is already a
+/// safe `<script>` element. Apply this before inserting the main content so
+/// placeholder-like text inside the content is left untouched.
+///
+/// # Arguments
+/// * `layout` - The HTML layout template
+/// * `meta` - The page metadata
+///
+/// # Returns
+/// * `String` - The layout with metadata filled in
+pub fn apply_page_meta(layout: &str, meta: &PageMeta) -> String {
+    replace_template(
+        layout,
+        &[
+            ("{{ page_title }}", &html::escape(&meta.title)),
+            ("{{ page_description }}", &html::escape(&meta.description)),
+            ("{{ canonical_url }}", &html::escape(&meta.canonical_url)),
+            ("{{ og_image }}", &html::escape(&meta.image_url)),
+            ("{{ structured_data }}", &meta.structured_data),
+        ],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,21 +210,44 @@ mod tests {
         assert_eq!(result, "Hello {{ name }}, welcome!");
     }
 
+    fn link(name: &str, url: &str) -> SiteLink {
+        SiteLink::new(name.to_string(), url.to_string())
+    }
+
     #[test]
     fn test_create_menu_html() {
-        let links = &[("Home", "/"), ("About", "/about")];
-        let result = create_menu_html(links);
+        let links = [link("Home", "/"), link("About", "/about")];
+        let result = create_menu_html(&links, "/contact/");
         let expected = "<li><a href=\"/\">Home</a></li>\n<li><a href=\"/about\">About</a></li>";
         assert_eq!(result, expected);
     }
 
     #[test]
     fn test_create_menu_html_empty() {
-        let links = &[];
-        let result = create_menu_html(links);
+        let result = create_menu_html(&[], "/");
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_create_menu_html_nested_active() {
+        let links = [
+            link("Projects", "/#projects").with_children(vec![
+                link("Reservoir", "/projects/reservoir/"),
+                link("A & B", "/projects/a\"b/"),
+            ]),
+            link("GitHub", "https://github.com/Sector-F-Labs"),
+        ];
+        assert_eq!(
+            create_menu_html(&links, "/projects/reservoir/"),
+            "<li class=\"active-trail\"><a href=\"/#projects\">Projects</a>\n<ul>\n<li class=\"active\"><a href=\"/projects/reservoir/\" aria-current=\"page\">Reservoir</a></li>\n<li><a href=\"/projects/a&quot;b/\">A &amp; B</a></li>\n</ul>\n</li>\n<li><a href=\"https://github.com/Sector-F-Labs\" target=\"_blank\" rel=\"noopener\">GitHub</a></li>"
+        );
+        // Pages below a link mark it as part of the trail
+        assert!(
+            create_menu_html(&links, "/projects/reservoir/releases/")
+                .contains("<li class=\"active-trail\"><a href=\"/projects/reservoir/\">")
+        );
+    }
+
     #[test]
     fn test_process_template() {
         let template = "<nav>{{ menu_items }}</nav><main>{{ main_content }}</main>";
@@ -151,6 +258,23 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_apply_page_meta() {
+        let layout = "<title>{{ page_title }}</title><link href=\"{{ canonical_url }}\"><meta content=\"{{ og_image }}\"><meta content=\"{{ page_description }}\"><main>{{ main_content }}</main>";
+        let meta = PageMeta {
+            title: "A & B".to_string(),
+            description: "Say \"hi\"".to_string(),
+            canonical_url: "https://example.com/a/".to_string(),
+            image_url: "https://example.com/a/logo.png".to_string(),
+            structured_data: String::new(),
+        };
+        let result = apply_page_meta(layout, &meta);
+        assert_eq!(
+            result,
+            "<title>A &amp; B</title><link href=\"https://example.com/a/\"><meta content=\"https://example.com/a/logo.png\"><meta content=\"Say &quot;hi&quot;\"><main>{{ main_content }}</main>"
+        );
+    }
+
     #[test]
     fn test_replace_template_multiple_occurrences() {
         let template = "{{ greeting }} {{ name }}, {{ greeting }} again {{ name }}!";
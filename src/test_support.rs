@@ -0,0 +1,83 @@
+//! Fixtures shared by unit tests.
+
+use git2::{Commit, Oid, Repository, Signature, Time};
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A fresh directory under the system temp dir, removed when dropped.
+///
+/// Names include the process ID and a counter, so parallel tests and later
+/// runs never share a directory, and it's cleaned up even when an assertion fails.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates an empty directory named `site-<name>-<pid>-<n>`.
+    pub fn new(name: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "site-{}-{}-{}",
+            name,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Commits `files` on top of HEAD in a bare or non-bare repository.
+///
+/// # Arguments
+/// * `repo` - The repository
+/// * `author` - Author and committer as `<name> <email>`, e.g. `Ada ada@example.com`
+/// * `seconds` - Commit time in Unix seconds; the offset is always `+02:00`
+/// * `files` - Paths and contents added to or replaced in the parent's tree
+///
+/// # Returns
+/// * `Oid` - The new commit
+pub fn commit(repo: &Repository, author: &str, seconds: i64, files: &[(&str, &str)]) -> Oid {
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let mut builder = repo
+        .treebuilder(parent.as_ref().map(|p| p.tree().unwrap()).as_ref())
+        .unwrap();
+    for (name, content) in files {
+        let blob = repo.blob(content.as_bytes()).unwrap();
+        builder.insert(name, blob, 0o100644).unwrap();
+    }
+    let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+    let (name, email) = author.split_once(' ').unwrap();
+    let signature = Signature::new(name, email, &Time::new(seconds, 120)).unwrap();
+    let parents: Vec<&Commit> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "change",
+        &tree,
+        &parents,
+    )
+    .unwrap()
+}
@@ -0,0 +1,45 @@
+//! Helpers for URL paths shared by the link checker and the preview server.
+
+/// Decodes `%XX` escapes in a URL path.
+///
+/// # Arguments
+/// * `path` - The path, e.g. `/tags/c%2B%2B/`
+///
+/// # Returns
+/// * `Option<String>` - The decoded path, or None for malformed escapes or invalid UTF-8
+pub fn percent_decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(
+            percent_decode("/tags/c%2B%2B%20%26%20%C3%BC/").as_deref(),
+            Some("/tags/c++ & ü/")
+        );
+        assert_eq!(percent_decode("/plain").as_deref(), Some("/plain"));
+        assert_eq!(percent_decode("/bad%zz"), None);
+        assert_eq!(percent_decode("/sign%+f"), None);
+        assert_eq!(percent_decode("/cut%2"), None);
+        assert_eq!(percent_decode("/%FF"), None);
+    }
+}
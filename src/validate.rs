@@ -0,0 +1,467 @@
+//! HTML well-formedness and accessibility lint for generated pages.
+//!
+//! Runs after a build over every page in the output directory. Pages are
+//! tokenised with a small tag scanner rather than a full HTML parser, which is
+//! enough for the markup this generator emits.
+
+use crate::check;
+use crate::pool;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Elements that never have a closing tag.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose closing tag may be omitted.
+const OPTIONAL_END: [&str; 9] = ["p", "li", "dt", "dd", "tr", "td", "th", "option", "tbody"];
+
+/// A lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rule {
+    /// Mismatched, unexpected or unclosed tags
+    Markup,
+    /// The same `id` used more than once on a page
+    DuplicateId,
+    /// `<img>` without an `alt` attribute
+    MissingAlt,
+    /// A heading more than one level deeper than the previous one
+    SkippedHeading,
+    /// `<html>` without a `lang` attribute
+    MissingLang,
+    /// `<button>` without text, `aria-label`, `aria-labelledby` or `title`
+    UnlabeledButton,
+}
+
+impl Rule {
+    fn label(self) -> &'static str {
+        match self {
+            Rule::Markup => "markup",
+            Rule::DuplicateId => "duplicate-id",
+            Rule::MissingAlt => "missing-alt",
+            Rule::SkippedHeading => "skipped-heading",
+            Rule::MissingLang => "missing-lang",
+            Rule::UnlabeledButton => "unlabeled-button",
+        }
+    }
+}
+
+/// How a rule's findings are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Reported and fails the build
+    Error,
+    /// Reported only
+    Warning,
+    /// Not reported
+    Off,
+}
+
+/// Severity for each rule.
+#[derive(Debug, Clone)]
+pub struct ValidationConfig {
+    severities: HashMap<Rule, Severity>,
+}
+
+impl Default for ValidationConfig {
+    /// Every rule is an error by default.
+    fn default() -> Self {
+        Self {
+            severities: HashMap::new(),
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Sets the severity of a rule.
+    ///
+    /// # Arguments
+    /// * `rule` - The rule to configure
+    /// * `severity` - Its new severity
+    pub fn set(&mut self, rule: Rule, severity: Severity) {
+        self.severities.insert(rule, severity);
+    }
+
+    /// Gets the severity of a rule.
+    pub fn severity(&self, rule: Rule) -> Severity {
+        self.severities
+            .get(&rule)
+            .copied()
+            .unwrap_or(Severity::Error)
+    }
+}
+
+/// A single finding on a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub rule: Rule,
+    pub severity: Severity,
+    /// 1-based line number in the page
+    pub line: usize,
+    pub message: String,
+}
+
+/// Findings for the whole site, grouped by page.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    /// Findings keyed by page path, sorted
+    pub pages: BTreeMap<String, Vec<Finding>>,
+    /// Number of pages validated
+    pub page_count: usize,
+}
+
+impl ValidationReport {
+    fn count(&self, severity: Severity) -> usize {
+        self.pages
+            .values()
+            .flatten()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    }
+
+    /// Counts findings that fail the build.
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    /// Prints the report grouped by page.
+    pub fn print(&self) {
+        println!("🩺 Validated {} pages", self.page_count);
+        for (page, findings) in &self.pages {
+            println!("{}", page);
+            for finding in findings {
+                let marker = match finding.severity {
+                    Severity::Error => "✗",
+                    _ => "!",
+                };
+                println!(
+                    "  {} {}:{} [{}] {}",
+                    marker,
+                    page,
+                    finding.line,
+                    finding.rule.label(),
+                    finding.message
+                );
+            }
+        }
+        println!(
+            "{} errors, {} warnings",
+            self.error_count(),
+            self.count(Severity::Warning)
+        );
+    }
+}
+
+/// Validates every HTML page under `output_dir`.
+///
+/// # Arguments
+/// * `output_dir` - The generated site directory, e.g. `docs`
+/// * `config` - Severity for each rule
+/// * `jobs` - Number of pages validated in parallel
+///
+/// # Returns
+/// * `Result<ValidationReport>` - The report, or an error if the output cannot be read
+pub fn validate_site(
+    output_dir: &str,
+    config: &ValidationConfig,
+    jobs: usize,
+) -> Result<ValidationReport> {
+    let mut pages = Vec::new();
+    check::collect_html_files(Path::new(output_dir), &mut pages)?;
+    pages.sort();
+
+    let mut report = ValidationReport {
+        page_count: pages.len(),
+        ..ValidationReport::default()
+    };
+    let results = pool::map(jobs, &pages, |page| {
+        fs::read_to_string(page)
+            .map(|html| validate_page(&html, config))
+            .map_err(|e| format!("Failed to read {}: {}", page.display(), e))
+    });
+    for (page, findings) in pages.iter().zip(results) {
+        let findings = findings?;
+        if !findings.is_empty() {
+            report.pages.insert(page.display().to_string(), findings);
+        }
+    }
+    Ok(report)
+}
+
+/// A start or end tag found in the page.
+struct Tag<'a> {
+    name: String,
+    closing: bool,
+    attributes: HashMap<String, &'a str>,
+    /// Byte offset of the `<`
+    start: usize,
+    /// Byte offset just past the `>`
+    end: usize,
+}
+
+/// Validates a single page.
+///
+/// # Arguments
+/// * `html` - The page source
+/// * `config` - Severity for each rule
+///
+/// # Returns
+/// * `Vec<Finding>` - Findings in source order, excluding rules that are off
+pub fn validate_page(html: &str, config: &ValidationConfig) -> Vec<Finding> {
+    let line_of = |offset: usize| html[..offset].matches('\n').count() + 1;
+    let mut findings: Vec<Finding> = Vec::new();
+    let mut report = |rule: Rule, offset: usize, message: String| {
+        let severity = config.severity(rule);
+        if severity != Severity::Off {
+            findings.push(Finding {
+                rule,
+                severity,
+                line: line_of(offset),
+                message,
+            });
+        }
+    };
+
+    let tags = tokenize(html);
+    let mut stack: Vec<&Tag> = Vec::new();
+    let mut ids: HashSet<&str> = HashSet::new();
+    let mut last_heading = 0;
+    let mut saw_html = false;
+
+    for (index, tag) in tags.iter().enumerate() {
+        if tag.closing {
+            match stack.iter().rposition(|open| open.name == tag.name) {
+                Some(position) => {
+                    for open in stack.drain(position..).skip(1) {
+                        if !OPTIONAL_END.contains(&open.name.as_str()) {
+                            report(
+                                Rule::Markup,
+                                open.start,
+                                format!("<{}> is not closed before </{}>", open.name, tag.name),
+                            );
+                        }
+                    }
+                }
+                None => report(
+                    Rule::Markup,
+                    tag.start,
+                    format!("Unexpected closing tag </{}>", tag.name),
+                ),
+            }
+            continue;
+        }
+
+        if let Some(id) = tag.attributes.get("id")
+            && !ids.insert(id)
+        {
+            report(
+                Rule::DuplicateId,
+                tag.start,
+                format!("Duplicate id \"{}\"", id),
+            );
+        }
+
+        match tag.name.as_str() {
+            "html" => {
+                saw_html = true;
+                if tag
+                    .attributes
+                    .get("lang")
+                    .is_none_or(|lang| lang.trim().is_empty())
+                {
+                    report(
+                        Rule::MissingLang,
+                        tag.start,
+                        "<html> is missing a lang attribute".to_string(),
+                    );
+                }
+            }
+            "img" if !tag.attributes.contains_key("alt") => {
+                let src = tag.attributes.get("src").copied().unwrap_or_default();
+                report(
+                    Rule::MissingAlt,
+                    tag.start,
+                    format!("<img src=\"{}\"> is missing alt text", src),
+                );
+            }
+            "button" => {
+                let labelled = ["aria-label", "aria-labelledby", "title"]
+                    .iter()
+                    .any(|attr| {
+                        tag.attributes
+                            .get(*attr)
+                            .is_some_and(|v| !v.trim().is_empty())
+                    });
+                let text = tags[index + 1..]
+                    .iter()
+                    .find(|t| t.closing && t.name == "button")
+                    .map(|close| crate::html::strip_tags(&html[tag.end..close.start]))
+                    .unwrap_or_default();
+                if !labelled && text.is_empty() {
+                    report(
+                        Rule::UnlabeledButton,
+                        tag.start,
+                        "<button> has no accessible label".to_string(),
+                    );
+                }
+            }
+            name if name.len() == 2 && name.starts_with('h') => {
+                if let Some(level) = name[1..]
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|l| (1..=6).contains(l))
+                {
+                    if last_heading > 0 && level > last_heading + 1 {
+                        report(
+                            Rule::SkippedHeading,
+                            tag.start,
+                            format!("<h{}> follows <h{}>", level, last_heading),
+                        );
+                    }
+                    last_heading = level;
+                }
+            }
+            _ => {}
+        }
+
+        if !VOID_ELEMENTS.contains(&tag.name.as_str()) {
+            stack.push(tag);
+        }
+    }
+
+    for open in stack {
+        if !OPTIONAL_END.contains(&open.name.as_str())
+            && open.name != "html"
+            && open.name != "body"
+            && open.name != "head"
+        {
+            report(
+                Rule::Markup,
+                open.start,
+                format!("<{}> is never closed", open.name),
+            );
+        }
+    }
+    if !saw_html {
+        report(
+            Rule::MissingLang,
+            0,
+            "Page has no <html> element".to_string(),
+        );
+    }
+
+    findings
+}
+
+/// Scans the page for start and end tags, skipping comments, the doctype and
+/// the contents of `<script>` and `<style>` elements.
+fn tokenize(html: &str) -> Vec<Tag<'_>> {
+    let tag_regex =
+        Regex::new(r"(?s)<!--.*?-->|<!\w[^>]*>|<(/?)([a-zA-Z][a-zA-Z0-9-]*)((?:[^>\x22']|\x22[^\x22]*\x22|'[^']*')*?)(/?)>")
+            .unwrap();
+    let attr_regex = Regex::new(
+        r#"([a-zA-Z_:][-a-zA-Z0-9_:.]*)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#,
+    )
+    .unwrap();
+
+    let mut tags = Vec::new();
+    let mut position = 0;
+    while let Some(caps) = tag_regex.captures_at(html, position) {
+        let whole = caps.get(0).unwrap();
+        position = whole.end();
+        let Some(name) = caps.get(2) else {
+            continue;
+        };
+
+        let name = name.as_str().to_lowercase();
+        let closing = !caps[1].is_empty();
+        let attributes = caps
+            .get(3)
+            .map(|attrs| {
+                attr_regex
+                    .captures_iter(attrs.as_str())
+                    .map(|attr| {
+                        let value = attr
+                            .get(2)
+                            .or(attr.get(3))
+                            .or(attr.get(4))
+                            .map(|m| m.as_str())
+                            .unwrap_or("");
+                        (attr[1].to_lowercase(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Raw text elements: jump straight to their closing tag
+        if !closing && (name == "script" || name == "style") {
+            let close = format!("</{}", name);
+            if let Some(offset) = html[position..].to_lowercase().find(&close) {
+                position += offset;
+            }
+        }
+
+        tags.push(Tag {
+            name,
+            closing,
+            attributes,
+            start: whole.start(),
+            end: whole.end(),
+        });
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(findings: &[Finding]) -> Vec<Rule> {
+        findings.iter().map(|finding| finding.rule).collect()
+    }
+
+    #[test]
+    fn test_valid_page() {
+        let html = "<!doctype html>\n<html lang=\"en\"><head><meta charset=\"UTF-8\" /><script>if (a < b) { x = \"</div>\"; }</script></head>\n<body><!-- <div> --><h1 id=\"a\">A</h1><h2>B</h2><p>One<p>Two<img src=\"x.png\" alt=\"\"><button class=\"copy-btn\">Copy</button></body></html>";
+        assert_eq!(validate_page(html, &ValidationConfig::default()), vec![]);
+    }
+
+    #[test]
+    fn test_invalid_page() {
+        let html = "<html><body>\n<h1 id=\"x\">A</h1>\n<h3 id=\"x\">C</h3>\n<div><span></div>\n<img src=\"a.png\">\n<button><i class=\"icon\"></i></button>\n</em></body></html>";
+        let findings = validate_page(html, &ValidationConfig::default());
+        assert_eq!(
+            rules(&findings),
+            vec![
+                Rule::MissingLang,
+                Rule::DuplicateId,
+                Rule::SkippedHeading,
+                Rule::Markup,
+                Rule::MissingAlt,
+                Rule::UnlabeledButton,
+                Rule::Markup,
+            ]
+        );
+        assert_eq!(findings[2].line, 3);
+        assert_eq!(findings[3].message, "<span> is not closed before </div>");
+        assert_eq!(findings[4].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_configured_severity() {
+        let mut config = ValidationConfig::default();
+        config.set(Rule::MissingAlt, Severity::Error);
+        config.set(Rule::MissingLang, Severity::Off);
+        let findings = validate_page("<html><img src=\"a.png\"></html>", &config);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, Rule::MissingAlt);
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+}